@@ -0,0 +1,228 @@
+use crate::scoring::{calculate_score_with_rules, ScoringRules};
+use crate::Symbol;
+use crate::Symbol::{Paper, Rock, Scissors};
+
+const SYMBOLS: [Symbol; 3] = [Rock, Paper, Scissors];
+const EPSILON: f64 = 1e-9;
+
+/// Probabilities for playing rock, paper and scissors, in that order.
+pub type Strategy = [f64; 3];
+
+pub const UNIFORM: Strategy = [1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0];
+
+/// Score of the row player for each combination; rows are the own shape, columns the opponent's.
+pub fn payoff_matrix(rules: &ScoringRules) -> [[i32; 3]; 3] {
+    let mut matrix = [[0; 3]; 3];
+    for (row, own) in SYMBOLS.iter().enumerate() {
+        for (column, opponent) in SYMBOLS.iter().enumerate() {
+            matrix[row][column] = calculate_score_with_rules(*opponent, *own, rules);
+        }
+    }
+    matrix
+}
+
+pub fn expected_score(rules: &ScoringRules, own: &Strategy, opponent: &Strategy) -> f64 {
+    let matrix = payoff_matrix(rules);
+    (0..3).flat_map(|row| (0..3).map(move |column| (row, column)))
+        .map(|(row, column)| own[row] * opponent[column] * matrix[row][column] as f64)
+        .sum()
+}
+
+/// Both players are scored by the same rules, so the game is symmetric. This finds every
+/// strategy that is a best response to itself by enumerating the possible supports.
+pub fn symmetric_equilibria(rules: &ScoringRules) -> Vec<Strategy> {
+    let matrix = payoff_matrix(rules).map(|row| row.map(|it| it as f64));
+    let mut equilibria: Vec<Strategy> = vec![];
+    for support_mask in 1..8usize {
+        let support = (0..3).filter(|it| support_mask & (1 << it) != 0).collect::<Vec<usize>>();
+        let Some(strategy) = solve_indifference(&matrix, &support) else { continue; };
+        let payoffs = (0..3).map(|row| (0..3).map(|column| matrix[row][column] * strategy[column]).sum::<f64>())
+            .collect::<Vec<f64>>();
+        let value = payoffs[support[0]];
+        let is_best_response = payoffs.iter().all(|it| *it <= value + EPSILON);
+        let is_known = equilibria.iter().any(|known| (0..3).all(|it| (known[it] - strategy[it]).abs() < EPSILON));
+        if is_best_response && !is_known {
+            equilibria.push(strategy);
+        }
+    }
+    equilibria
+}
+
+/// Solves for a strategy on `support` that makes every shape in the support equally good.
+fn solve_indifference(matrix: &[[f64; 3]; 3], support: &[usize]) -> Option<Strategy> {
+    // unknowns are the probabilities of the support followed by the common payoff
+    let size = support.len() + 1;
+    let mut system = vec![vec![0.0; size + 1]; size];
+    for (equation, row) in support.iter().enumerate() {
+        for (unknown, column) in support.iter().enumerate() {
+            system[equation][unknown] = matrix[*row][*column];
+        }
+        system[equation][size - 1] = -1.0;
+    }
+    system[size - 1][..support.len()].fill(1.0);
+    system[size - 1][size] = 1.0;
+
+    let solution = gaussian_elimination(system)?;
+    let mut strategy = [0.0; 3];
+    for (unknown, column) in support.iter().enumerate() {
+        if solution[unknown] < -EPSILON {
+            return None;
+        }
+        strategy[*column] = solution[unknown].max(0.0);
+    }
+    Some(strategy)
+}
+
+fn gaussian_elimination(mut system: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let size = system.len();
+    for pivot in 0..size {
+        let best = (pivot..size).max_by(|a, b| system[*a][pivot].abs().total_cmp(&system[*b][pivot].abs()))?;
+        if system[best][pivot].abs() < EPSILON {
+            return None;
+        }
+        system.swap(pivot, best);
+        let pivot_row = system[pivot].clone();
+        for (row, values) in system.iter_mut().enumerate() {
+            if row != pivot {
+                let factor = values[pivot] / pivot_row[pivot];
+                values.iter_mut().zip(&pivot_row).skip(pivot).for_each(|(value, it)| *value -= factor * it);
+            }
+        }
+    }
+    Some((0..size).map(|it| system[it][size] / system[it][it]).collect())
+}
+
+#[derive(Debug)]
+pub struct GuideAnalysis {
+    pub rounds: usize,
+    pub guide_average: f64,
+    pub random_average: f64,
+    pub equilibrium_average: Option<f64>,
+}
+
+/// Compares following the guide with random and equilibrium play against the opponent's
+/// observed shape frequencies. Rounds are given as (opponent, own) pairs. Without any rounds
+/// there are no averages, so `None` is returned.
+pub fn analyze_guide(rounds: &[(Symbol, Symbol)], rules: &ScoringRules) -> Option<GuideAnalysis> {
+    if rounds.is_empty() {
+        return None;
+    }
+    let total: i32 = rounds.iter().map(|(a, b)| calculate_score_with_rules(*a, *b, rules)).sum();
+    let mut opponent = [0.0; 3];
+    rounds.iter().for_each(|(a, _)| opponent[symbol_index(a)] += 1.0 / rounds.len() as f64);
+
+    Some(GuideAnalysis {
+        rounds: rounds.len(),
+        guide_average: total as f64 / rounds.len() as f64,
+        random_average: expected_score(rules, &UNIFORM, &opponent),
+        equilibrium_average: symmetric_equilibria(rules).first().map(|it| expected_score(rules, it, &opponent)),
+    })
+}
+
+fn symbol_index(symbol: &Symbol) -> usize {
+    SYMBOLS.iter().position(|it| it == symbol).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_strategy_eq(expected: Strategy, actual: Strategy) {
+        assert!((0..3).all(|it| (expected[it] - actual[it]).abs() < 1e-6), "{expected:?} != {actual:?}");
+    }
+
+    #[test]
+    fn builds_payoff_matrix() {
+        // given: the default rules
+        let rules = ScoringRules::default();
+
+        // when: the payoff matrix is built
+        let result = payoff_matrix(&rules);
+
+        // then: each cell is the score of the own shape against the opponent's
+        assert_eq!(result, [[4, 1, 7], [8, 5, 2], [3, 9, 6]]);
+    }
+
+    #[test]
+    fn calculates_expected_score_of_mixed_strategies() {
+        // given: uniform play against an opponent always choosing rock
+        let rules = ScoringRules::default();
+
+        // when: the expected score is calculated
+        let result = expected_score(&rules, &UNIFORM, &[1.0, 0.0, 0.0]);
+
+        // then: we get the average of the rock column
+        assert!((result - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn finds_uniform_equilibrium_without_shape_values() {
+        // given: rules where only the outcome is rewarded
+        let rules = ScoringRules { rock: 0, paper: 0, scissors: 0, ..ScoringRules::default() };
+
+        // when: equilibria are computed
+        let result = symmetric_equilibria(&rules);
+
+        // then: the only equilibrium is uniform play
+        assert_eq!(result.len(), 1);
+        assert_strategy_eq(UNIFORM, result[0]);
+    }
+
+    #[test]
+    fn finds_equilibrium_for_default_rules() {
+        // given: the default rules
+        let rules = ScoringRules::default();
+
+        // when: equilibria are computed
+        let result = symmetric_equilibria(&rules);
+
+        // then: every shape yields the same expected score against the equilibrium
+        assert_eq!(result.len(), 1);
+        let payoffs = SYMBOLS.map(|it| {
+            let mut own = [0.0; 3];
+            own[symbol_index(&it)] = 1.0;
+            expected_score(&rules, &own, &result[0])
+        });
+        assert!((payoffs[0] - payoffs[1]).abs() < 1e-6 && (payoffs[1] - payoffs[2]).abs() < 1e-6);
+    }
+
+    #[test]
+    fn finds_pure_equilibrium_when_a_shape_dominates() {
+        // given: rules where paper is worth so much that it always pays off
+        let rules = ScoringRules { paper: 100, ..ScoringRules::default() };
+
+        // when: equilibria are computed
+        let result = symmetric_equilibria(&rules);
+
+        // then: always playing paper is the equilibrium
+        assert_eq!(result.len(), 1);
+        assert_strategy_eq([0.0, 1.0, 0.0], result[0]);
+    }
+
+    #[test]
+    fn analyzes_strategy_guide() {
+        // given: the example guide
+        let rounds = vec![(Rock, Paper), (Paper, Rock), (Scissors, Scissors)];
+
+        // when: the guide is analyzed
+        let result = analyze_guide(&rounds, &ScoringRules::default()).unwrap();
+
+        // then: the guide average is compared to random play
+        assert_eq!(result.rounds, 3);
+        assert!((result.guide_average - 5.0).abs() < 1e-9);
+        assert!((result.random_average - 5.0).abs() < 1e-9);
+        assert!(result.equilibrium_average.is_some());
+    }
+
+    #[test]
+    fn does_not_analyze_empty_guide() {
+        // given: a guide without rounds, e.g. after skipping every line in lenient mode
+        let rounds = vec![];
+
+        // when: the guide is analyzed
+        let result = analyze_guide(&rounds, &ScoringRules::default());
+
+        // then: there is nothing to average
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+
+use crate::{map_aliases, Symbol};
+
+#[derive(Debug, PartialEq)]
+pub struct GuideError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for GuideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Guide {
+    /// (opponent, second column) pairs in input order.
+    pub rounds: Vec<(Symbol, Symbol)>,
+    /// Malformed lines that were dropped in lenient mode.
+    pub skipped: Vec<GuideError>,
+}
+
+/// Parses a strategy guide. Columns may be separated by any whitespace and blank lines are
+/// ignored. In strict mode every malformed line is reported, in lenient mode they are skipped.
+pub fn parse_guide(input: &str, lenient: bool) -> Result<Guide, Vec<GuideError>> {
+    let mut rounds = vec![];
+    let mut errors = vec![];
+    for (index, line) in input.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().is_empty() {
+            continue;
+        }
+        match parse_line(line, index + 1) {
+            Ok(round) => rounds.push(round),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if lenient || errors.is_empty() {
+        Ok(Guide { rounds, skipped: errors })
+    } else {
+        Err(errors)
+    }
+}
+
+fn parse_line(line: &str, number: usize) -> Result<(Symbol, Symbol), GuideError> {
+    let error = |column: usize, message: String| GuideError { line: number, column, message };
+    // columns are counted in characters, so a multi-byte character before a word doesn't shift it
+    let columns = line.char_indices().enumerate()
+        .filter(|(_, (index, char))| !char.is_whitespace() && line[..*index].chars().next_back().is_none_or(char::is_whitespace))
+        .map(|(column, (index, _))| (column + 1, line[index..].split_whitespace().next().unwrap()))
+        .collect::<Vec<(usize, &str)>>();
+    match columns.as_slice() {
+        [(a_column, a), (b_column, b)] => {
+            let opponent = parse_column(a, ["A", "B", "C"]).ok_or_else(|| error(*a_column, format!("expected A, B or C, got `{a}`")))?;
+            let own = parse_column(b, ["X", "Y", "Z"]).ok_or_else(|| error(*b_column, format!("expected X, Y or Z, got `{b}`")))?;
+            Ok((opponent, own))
+        }
+        [_] => Err(error(line.trim_end().chars().count() + 1, "missing second column".to_string())),
+        _ => Err(error(columns[2].0, format!("expected 2 columns, got {}", columns.len()))),
+    }
+}
+
+fn parse_column(value: &str, allowed: [&str; 3]) -> Option<Symbol> {
+    allowed.iter().any(|it| it.eq_ignore_ascii_case(value)).then(|| map_aliases(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Symbol::{Paper, Rock, Scissors};
+
+    #[test]
+    fn parses_guide_with_irregular_whitespace_and_crlf() {
+        // given: a guide with tabs, repeated spaces, trailing whitespace and CRLF line endings
+        let input = "A Y\r\nB\tX  \r\n  C   Z\r\n\r\n";
+
+        // when: the guide is parsed strictly
+        let result = parse_guide(input, false).unwrap();
+
+        // then: all rounds are read
+        assert_eq!(result.rounds, vec![(Rock, Paper), (Paper, Rock), (Scissors, Scissors)]);
+        assert!(result.skipped.is_empty());
+    }
+
+    #[test]
+    fn reports_every_malformed_line_with_position() {
+        // given: a guide with a missing column, an unknown symbol, swapped columns and an extra column
+        let input = "A Y\nB\nA  Q\nX A\nA Y Z";
+
+        // when: the guide is parsed strictly
+        let result = parse_guide(input, false).unwrap_err();
+
+        // then: each bad line is reported with its line and column
+        let positions = result.iter().map(|it| (it.line, it.column)).collect::<Vec<(usize, usize)>>();
+        assert_eq!(positions, vec![(2, 2), (3, 4), (4, 1), (5, 5)]);
+        assert_eq!(result[1].to_string(), "3:4: expected X, Y or Z, got `Q`");
+    }
+
+    #[test]
+    fn counts_columns_in_characters() {
+        // given: lines with multi-byte characters before the offending column
+        let input = "A\u{3000}Q
+Ä\u{3000}Y Z
+Ä";
+
+        // when: the guide is parsed strictly
+        let result = parse_guide(input, false).unwrap_err();
+
+        // then: the columns are counted in characters rather than bytes
+        let positions = result.iter().map(|it| (it.line, it.column)).collect::<Vec<(usize, usize)>>();
+        assert_eq!(positions, vec![(1, 3), (2, 5), (3, 2)]);
+    }
+
+    #[test]
+    fn skips_malformed_lines_in_lenient_mode() {
+        // given: a guide with two bad lines
+        let input = "A Y\nB\nC Z\nD X";
+
+        // when: the guide is parsed leniently
+        let result = parse_guide(input, true).unwrap();
+
+        // then: the valid rounds are kept and the bad lines are counted
+        assert_eq!(result.rounds, vec![(Rock, Paper), (Scissors, Scissors)]);
+        assert_eq!(result.skipped.len(), 2);
+    }
+}
@@ -1,31 +1,86 @@
+use crate::analysis::{analyze_guide, symmetric_equilibria};
+use crate::guide::parse_guide;
+use crate::repl::{run_session, AdaptiveBot, Bot, GuideBot};
+use crate::scoring::{calculate_score_with_rules, ScoringRules};
+use crate::tournament::{league_table, parse_rounds, run_tournament};
 use crate::Symbol::{Paper, Rock, Scissors};
 
+mod analysis;
+mod guide;
+mod repl;
+mod scoring;
+mod tournament;
+
 fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    let rules = match args.iter().position(|it| it == "--rules") {
+        Some(index) => ScoringRules::from_file(args.get(index + 1).expect("--rules needs a path")).unwrap(),
+        None => ScoringRules::default(),
+    };
+
+    if let Some(index) = args.iter().position(|it| it == "--tournament") {
+        let path = args.get(index + 1).expect("--tournament needs a path");
+        let input = std::fs::read_to_string(path).unwrap();
+        let rounds = match parse_rounds(&input) {
+            Ok(rounds) => rounds,
+            Err(error) => {
+                eprintln!("{path}: {error}");
+                std::process::exit(1);
+            }
+        };
+        let standings = run_tournament(&rounds, &rules);
+        println!("{:<16} {:>6} {:>4} {:>4} {:>4}", "Player", "Points", "W", "D", "L");
+        for (name, standing) in league_table(&standings) {
+            let record = standing.record;
+            println!("{name:<16} {:>6} {:>4} {:>4} {:>4}", standing.points, record.wins, record.draws, record.losses);
+            for (opponent, record) in &standing.head_to_head {
+                println!("  vs {opponent:<12} {:>10} {:>4} {:>4}", record.wins, record.draws, record.losses);
+            }
+        }
+        return;
+    }
+
     let input = std::fs::read_to_string("input.txt").unwrap();
-    let score: i32 = input.lines().map(|line| {
-        let split = line.split(" ").map(map_aliases).collect::<Vec<Symbol>>();
-        calculate_score(split[0], split[1])
-    }).sum();
-    println!("The score is {score}");
+    let guide = match parse_guide(&input, args.iter().any(|it| it == "--lenient")) {
+        Ok(guide) => guide,
+        Err(errors) => {
+            errors.iter().for_each(|it| eprintln!("input.txt:{it}"));
+            std::process::exit(1);
+        }
+    };
+    if !guide.skipped.is_empty() {
+        eprintln!("Skipped {} malformed lines", guide.skipped.len());
+    }
+    let rounds = guide.rounds;
+
+    if let Some(index) = args.iter().position(|it| it == "--play") {
+        let mut bot: Box<dyn Bot> = match args.get(index + 1).map(|it| it.as_str()) {
+            Some("adaptive") => Box::<AdaptiveBot>::default(),
+            _ => Box::new(GuideBot::new(&rounds)),
+        };
+        run_session(std::io::stdin().lock(), &mut std::io::stdout(), bot.as_mut(), &rules).unwrap();
+        return;
+    }
 
-    let score: i32 = input.lines().map(|line| {
-        let split = line.split(" ").map(map_aliases).collect::<Vec<Symbol>>();
-        calculate_score(split[0], map_symbol_for_outcome(split[0], split[1]))
-    }).sum();
+    let score: i32 = rounds.iter().map(|(a, b)| calculate_score_with_rules(*a, *b, &rules)).sum();
     println!("The score is {score}");
-}
 
-fn calculate_score(a: Symbol, b: Symbol) -> i32 {
-    calculate_reward(&a, &b) + b.get_value()
-}
+    let score: i32 = rounds.iter().map(|(a, b)| calculate_score_with_rules(*a, map_symbol_for_outcome(*a, *b), &rules)).sum();
+    println!("The score is {score}");
 
-fn calculate_reward(a: &Symbol, b: &Symbol) -> i32 {
-    if a == b {
-        return 3;
-    } else if &a.looses_against() == b {
-        return 6;
+    if args.iter().any(|it| it == "--analyze") {
+        let Some(analysis) = analyze_guide(&rounds, &rules) else {
+            eprintln!("The guide has no rounds to analyze");
+            return;
+        };
+        println!("Analyzed {} rounds", analysis.rounds);
+        println!("Equilibria (rock, paper, scissors): {:?}", symmetric_equilibria(&rules));
+        println!("Average score following the guide: {:.3}", analysis.guide_average);
+        println!("Average score playing randomly: {:.3}", analysis.random_average);
+        if let Some(average) = analysis.equilibrium_average {
+            println!("Average score playing the equilibrium: {average:.3}");
+        }
     }
-    0
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -37,15 +92,19 @@ enum Symbol {
 
 impl Symbol {
     fn from_str(s: &str) -> Symbol {
+        Symbol::try_from_str(s).expect("Unknown symbol")
+    }
+
+    fn try_from_str(s: &str) -> Option<Symbol> {
         let s = s.to_uppercase();
         match s.as_str() {
-            "A" => Rock,
-            "X" => Rock,
-            "B" => Paper,
-            "Y" => Paper,
-            "C" => Scissors,
-            "Z" => Scissors,
-            _ => panic!("Unknown symbol"),
+            "A" => Some(Rock),
+            "X" => Some(Rock),
+            "B" => Some(Paper),
+            "Y" => Some(Paper),
+            "C" => Some(Scissors),
+            "Z" => Some(Scissors),
+            _ => None,
         }
     }
 
@@ -64,14 +123,6 @@ impl Symbol {
             Scissors => Rock,
         }
     }
-
-    fn get_value(&self) -> i32 {
-        match self {
-            Rock => 1,
-            Paper => 2,
-            Scissors => 3,
-        }
-    }
 }
 
 fn map_aliases(alias: &str) -> Symbol {
@@ -157,8 +208,9 @@ mod tests {
             (Scissors, Scissors, 6),
         ];
 
-        // when: calculate_score is invoked
-        let output: Vec<i32> = invocations_expectations.iter().map(|it| calculate_score(it.0, it.1)).collect();
+        // when: calculate_score_with_rules is invoked with the default rules
+        let rules = ScoringRules::default();
+        let output: Vec<i32> = invocations_expectations.iter().map(|it| calculate_score_with_rules(it.0, it.1, &rules)).collect();
 
         // then: correct score is returned
         let expected: Vec<i32> = invocations_expectations.iter().map(|it| it.2).collect();
@@ -0,0 +1,178 @@
+use std::io::{BufRead, Write};
+
+use crate::scoring::{calculate_score_with_rules, ScoringRules};
+use crate::Symbol::{Paper, Rock, Scissors};
+use crate::{map_symbol_for_outcome, Symbol};
+
+pub trait Bot {
+    fn next_move(&mut self) -> Symbol;
+
+    /// Called after every round with the shape the player chose.
+    fn observe(&mut self, _player: Symbol) {}
+}
+
+/// Plays the second column of a strategy guide, starting over once it runs out.
+pub struct GuideBot {
+    moves: Vec<Symbol>,
+    position: usize,
+}
+
+impl GuideBot {
+    pub fn new(rounds: &[(Symbol, Symbol)]) -> Self {
+        GuideBot { moves: rounds.iter().map(|(_, it)| *it).collect(), position: 0 }
+    }
+}
+
+impl Bot for GuideBot {
+    fn next_move(&mut self) -> Symbol {
+        let symbol = self.moves.get(self.position % self.moves.len().max(1)).copied().unwrap_or(Rock);
+        self.position += 1;
+        symbol
+    }
+}
+
+/// Predicts the player's next shape from what they played after their previous shape and
+/// plays the shape that beats it.
+#[derive(Default)]
+pub struct AdaptiveBot {
+    transitions: [[usize; 3]; 3],
+    last: Option<Symbol>,
+}
+
+impl Bot for AdaptiveBot {
+    fn next_move(&mut self) -> Symbol {
+        let predicted = self.last
+            .map(|it| self.transitions[index(it)])
+            .filter(|it| it.iter().any(|count| *count > 0))
+            .map(|counts| [Rock, Paper, Scissors][(0..3).max_by_key(|it| counts[*it]).unwrap()])
+            .unwrap_or(Rock);
+        map_symbol_for_outcome(predicted, Scissors)
+    }
+
+    fn observe(&mut self, player: Symbol) {
+        if let Some(last) = self.last {
+            self.transitions[index(last)][index(player)] += 1;
+        }
+        self.last = Some(player);
+    }
+}
+
+fn index(symbol: Symbol) -> usize {
+    match symbol {
+        Rock => 0,
+        Paper => 1,
+        Scissors => 2,
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Session {
+    pub rounds: usize,
+    pub player_score: i32,
+    pub bot_score: i32,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Session {
+    pub fn play(&mut self, player: Symbol, bot: Symbol, rules: &ScoringRules) {
+        self.rounds += 1;
+        self.player_score += calculate_score_with_rules(bot, player, rules);
+        self.bot_score += calculate_score_with_rules(player, bot, rules);
+        if player == bot {
+            self.draws += 1;
+        } else if player.wins_against() == bot {
+            self.wins += 1;
+        } else {
+            self.losses += 1;
+        }
+    }
+}
+
+fn parse_move(input: &str) -> Option<Symbol> {
+    match input.to_lowercase().as_str() {
+        "r" | "rock" => Some(Rock),
+        "p" | "paper" => Some(Paper),
+        "s" | "scissors" => Some(Scissors),
+        _ => None,
+    }
+}
+
+/// Reads one command per line until `quit` or the end of the input and returns the final score.
+pub fn run_session<R: BufRead, W: Write>(input: R, output: &mut W, bot: &mut dyn Bot, rules: &ScoringRules) -> std::io::Result<Session> {
+    let mut session = Session::default();
+    writeln!(output, "Play rock, paper or scissors (r/p/s). Type `score` for the standings or `quit` to leave.")?;
+    for line in input.lines() {
+        let line = line?;
+        let command = line.trim();
+        match command {
+            "" => continue,
+            "quit" | "exit" => break,
+            "score" => writeln!(output, "{}", format_score(&session))?,
+            _ => match parse_move(command) {
+                Some(player) => {
+                    let opponent = bot.next_move();
+                    session.play(player, opponent, rules);
+                    bot.observe(player);
+                    let outcome = if player == opponent { "Draw" } else if player.wins_against() == opponent { "You win" } else { "You lose" };
+                    writeln!(output, "{player:?} vs {opponent:?}: {outcome}. {}", format_score(&session))?;
+                }
+                None => writeln!(output, "Unknown command `{command}`")?,
+            },
+        }
+    }
+    writeln!(output, "Final score after {} rounds: {}", session.rounds, format_score(&session))?;
+    Ok(session)
+}
+
+fn format_score(session: &Session) -> String {
+    format!("You {} - {} Bot ({}W/{}D/{}L)", session.player_score, session.bot_score, session.wins, session.draws, session.losses)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guide_bot_cycles_through_guide() {
+        // given: a bot following a guide of two rounds
+        let mut bot = GuideBot::new(&[(Rock, Paper), (Paper, Scissors)]);
+
+        // when: it plays three rounds
+        let result = (0..3).map(|_| bot.next_move()).collect::<Vec<Symbol>>();
+
+        // then: it starts over after the guide ends
+        assert_eq!(result, vec![Paper, Scissors, Paper]);
+    }
+
+    #[test]
+    fn adaptive_bot_counters_repeated_pattern() {
+        // given: a bot that has seen the player alternate rock and paper
+        let mut bot = AdaptiveBot::default();
+        [Rock, Paper, Rock, Paper, Rock].into_iter().for_each(|it| bot.observe(it));
+
+        // when: it picks its next move
+        let result = bot.next_move();
+
+        // then: it beats the predicted paper
+        assert_eq!(result, Scissors);
+    }
+
+    #[test]
+    fn plays_scripted_session() {
+        // given: a scripted session against a bot always playing rock
+        let mut bot = GuideBot::new(&[(Rock, Rock)]);
+        let input = "paper\nS\nrock\nlizard\nscore\nquit\npaper\n".as_bytes();
+        let mut output = vec![];
+
+        // when: the session is run
+        let result = run_session(input, &mut output, &mut bot, &ScoringRules::default()).unwrap();
+
+        // then: the session score accumulates until quit
+        assert_eq!(result, Session { rounds: 3, player_score: 8 + 3 + 4, bot_score: 1 + 7 + 4, wins: 1, draws: 1, losses: 1 });
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Unknown command `lizard`"));
+        assert!(output.contains("Final score after 3 rounds: You 15 - 12 Bot (1W/1D/1L)"));
+    }
+}
@@ -0,0 +1,132 @@
+use crate::Symbol;
+use crate::Symbol::{Paper, Rock, Scissors};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ScoringRules {
+    pub rock: i32,
+    pub paper: i32,
+    pub scissors: i32,
+    pub loss: i32,
+    pub draw: i32,
+    pub win: i32,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        ScoringRules { rock: 1, paper: 2, scissors: 3, loss: 0, draw: 3, win: 6 }
+    }
+}
+
+impl ScoringRules {
+    /// Reads rules from a file of `key = value` lines. Keys that are not present keep their
+    /// default value, lines starting with `#` are ignored.
+    pub fn from_file(path: &str) -> Result<ScoringRules, String> {
+        let input = std::fs::read_to_string(path).map_err(|err| format!("Cannot read {path}: {err}"))?;
+        ScoringRules::parse(&input)
+    }
+
+    pub fn parse(input: &str) -> Result<ScoringRules, String> {
+        let mut rules = ScoringRules::default();
+        for (index, line) in input.lines().enumerate().map(|(index, it)| (index + 1, it.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line.split_once('=')
+                .ok_or(format!("Line {index}: expected `key = value`, got `{line}`"))?;
+            let value = value.trim().parse::<i32>()
+                .map_err(|_| format!("Line {index}: `{}` is not a number", value.trim()))?;
+            match key.trim().to_lowercase().as_str() {
+                "rock" => rules.rock = value,
+                "paper" => rules.paper = value,
+                "scissors" => rules.scissors = value,
+                "loss" => rules.loss = value,
+                "draw" => rules.draw = value,
+                "win" => rules.win = value,
+                key => return Err(format!("Line {index}: unknown key `{key}`")),
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn shape_value(&self, symbol: &Symbol) -> i32 {
+        match symbol {
+            Rock => self.rock,
+            Paper => self.paper,
+            Scissors => self.scissors,
+        }
+    }
+
+    /// Reward for the player choosing `b` against an opponent choosing `a`.
+    pub fn outcome_reward(&self, a: &Symbol, b: &Symbol) -> i32 {
+        if a == b {
+            return self.draw;
+        } else if &a.looses_against() == b {
+            return self.win;
+        }
+        self.loss
+    }
+}
+
+pub fn calculate_score_with_rules(a: Symbol, b: Symbol, rules: &ScoringRules) -> i32 {
+    rules.outcome_reward(&a, &b) + rules.shape_value(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_rules_match_puzzle_scoring() {
+        // given: the default rules
+        let rules = ScoringRules::default();
+
+        // when: scores are calculated for the example guide
+        let result = vec![(Rock, Paper), (Paper, Rock), (Scissors, Scissors)].into_iter()
+            .map(|(a, b)| calculate_score_with_rules(a, b, &rules))
+            .collect::<Vec<i32>>();
+
+        // then: we get the puzzle scores
+        assert_eq!(result, vec![8, 1, 6]);
+    }
+
+    #[test]
+    fn parses_rules_from_config() {
+        // given: a config overriding some values
+        let input = r#"# tournament variant
+            scissors = 5
+            win = 10
+            Draw=1"#;
+
+        // when: the config is parsed
+        let result = ScoringRules::parse(input).unwrap();
+
+        // then: overridden values are applied and the rest keeps the defaults
+        assert_eq!(result, ScoringRules { rock: 1, paper: 2, scissors: 5, loss: 0, draw: 1, win: 10 });
+    }
+
+    #[test]
+    fn rejects_invalid_config_lines() {
+        // given: configs with mistakes
+        let inputs = ["rock 1", "rock = one", "lizard = 4"];
+
+        // when: they are parsed
+        let result = inputs.iter().map(|it| ScoringRules::parse(it)).collect::<Vec<_>>();
+
+        // then: every config is rejected
+        assert!(result.iter().all(|it| it.is_err()));
+    }
+
+    #[test]
+    fn calculates_score_with_custom_rules() {
+        // given: rules with a different reward schedule
+        let rules = ScoringRules { rock: 0, paper: 0, scissors: 0, loss: -1, draw: 0, win: 1 };
+
+        // when: scores are calculated
+        let result = vec![(Rock, Paper), (Paper, Rock), (Scissors, Scissors)].into_iter()
+            .map(|(a, b)| calculate_score_with_rules(a, b, &rules))
+            .collect::<Vec<i32>>();
+
+        // then: only the outcome counts
+        assert_eq!(result, vec![1, -1, 0]);
+    }
+}
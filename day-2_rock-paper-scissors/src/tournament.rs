@@ -0,0 +1,159 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::scoring::{calculate_score_with_rules, ScoringRules};
+use crate::Symbol;
+
+/// One round of a tournament: every player with the shape they chose.
+pub type Round = Vec<(String, Symbol)>;
+
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub struct Record {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Standing {
+    pub points: i32,
+    pub record: Record,
+    pub head_to_head: BTreeMap<String, Record>,
+}
+
+/// Parses rounds of `name=SYMBOL` entries separated by whitespace, e.g. `alice=A bob=Y carol=C`.
+/// A player may only appear once per round, as they would otherwise play against themselves.
+pub fn parse_rounds(input: &str) -> Result<Vec<Round>, String> {
+    input.lines().enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let round = line.split_whitespace().map(|entry| {
+                let (name, symbol) = entry.split_once('=')
+                    .ok_or(format!("Line {}: expected `name=SYMBOL`, got `{entry}`", index + 1))?;
+                let symbol = Symbol::try_from_str(symbol)
+                    .ok_or(format!("Line {}: unknown symbol `{symbol}` for {name}", index + 1))?;
+                Ok((name.to_string(), symbol))
+            }).collect::<Result<Round, String>>()?;
+            let mut names = BTreeSet::new();
+            if let Some((name, _)) = round.iter().find(|(name, _)| !names.insert(name)) {
+                return Err(format!("Line {}: duplicate player {name}", index + 1));
+            }
+            Ok(round)
+        })
+        .collect()
+}
+
+/// Scores every pairwise match of every round and returns the standings per player.
+pub fn run_tournament(rounds: &[Round], rules: &ScoringRules) -> BTreeMap<String, Standing> {
+    let mut standings: BTreeMap<String, Standing> = BTreeMap::new();
+    for round in rounds {
+        for (index, (player, own)) in round.iter().enumerate() {
+            for (opponent, other) in round.iter().skip(index + 1) {
+                record_match(&mut standings, player, opponent, *other, *own, rules);
+                record_match(&mut standings, opponent, player, *own, *other, rules);
+            }
+        }
+    }
+    standings
+}
+
+fn record_match(standings: &mut BTreeMap<String, Standing>, player: &str, opponent: &str, a: Symbol, b: Symbol, rules: &ScoringRules) {
+    let standing = standings.entry(player.to_string()).or_default();
+    standing.points += calculate_score_with_rules(a, b, rules);
+    let head_to_head = standing.head_to_head.entry(opponent.to_string()).or_default();
+    if a == b {
+        standing.record.draws += 1;
+        head_to_head.draws += 1;
+    } else if b.wins_against() == a {
+        standing.record.wins += 1;
+        head_to_head.wins += 1;
+    } else {
+        standing.record.losses += 1;
+        head_to_head.losses += 1;
+    }
+}
+
+/// Orders the standings by points, then wins, then name.
+pub fn league_table(standings: &BTreeMap<String, Standing>) -> Vec<(&String, &Standing)> {
+    let mut table = standings.iter().collect::<Vec<_>>();
+    table.sort_by(|a, b| b.1.points.cmp(&a.1.points)
+        .then(b.1.record.wins.cmp(&a.1.record.wins))
+        .then(a.0.cmp(b.0)));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Symbol::{Paper, Rock, Scissors};
+
+    #[test]
+    fn parses_rounds_with_named_players() {
+        // given: rounds with three players
+        let input = r#"alice=A bob=Y carol=C
+            alice=b  bob=x carol=Z"#;
+
+        // when: the rounds are parsed
+        let result = parse_rounds(input).unwrap();
+
+        // then: every player is mapped to the chosen symbol
+        assert_eq!(result, vec![
+            vec![("alice".to_string(), Rock), ("bob".to_string(), Paper), ("carol".to_string(), Scissors)],
+            vec![("alice".to_string(), Paper), ("bob".to_string(), Rock), ("carol".to_string(), Scissors)],
+        ]);
+    }
+
+    #[test]
+    fn rejects_malformed_rounds() {
+        // given: rounds with a missing separator and an unknown symbol
+        let inputs = ["alice=A bob", "alice=A bob=Q"];
+
+        // when: the rounds are parsed
+        let result = inputs.iter().map(|it| parse_rounds(it)).collect::<Vec<_>>();
+
+        // then: both are rejected
+        assert!(result.iter().all(|it| it.is_err()));
+    }
+
+    #[test]
+    fn rejects_players_appearing_twice_in_a_round() {
+        // given: a round where alice plays twice
+        let input = "alice=A bob=B\nalice=A alice=B bob=C";
+
+        // when: the rounds are parsed
+        let result = parse_rounds(input);
+
+        // then: the duplicate player is reported with the line
+        assert_eq!(result, Err("Line 2: duplicate player alice".to_string()));
+    }
+
+    #[test]
+    fn scores_every_pairwise_match() {
+        // given: a round where every shape is played once
+        let rounds = parse_rounds("alice=A bob=B carol=C").unwrap();
+
+        // when: the tournament is run
+        let result = run_tournament(&rounds, &ScoringRules::default());
+
+        // then: each player wins once and loses once
+        assert_eq!(result["alice"].points, 1 + 6 + 1);
+        assert_eq!(result["bob"].points, 2 + 6 + 2);
+        assert_eq!(result["carol"].points, 3 + 6 + 3);
+        assert!(result.values().all(|it| it.record == Record { wins: 1, draws: 0, losses: 1 }));
+        assert_eq!(result["alice"].head_to_head["carol"], Record { wins: 1, draws: 0, losses: 0 });
+        assert_eq!(result["alice"].head_to_head["bob"], Record { wins: 0, draws: 0, losses: 1 });
+    }
+
+    #[test]
+    fn orders_league_table_by_points() {
+        // given: two rounds of a tournament
+        let rounds = parse_rounds(r#"alice=A bob=B carol=B
+            alice=A bob=A carol=B"#).unwrap();
+
+        // when: the league table is built
+        let standings = run_tournament(&rounds, &ScoringRules::default());
+        let result = league_table(&standings).iter().map(|(name, _)| name.as_str()).collect::<Vec<&str>>();
+
+        // then: players are ordered by points
+        assert_eq!(result, vec!["carol", "bob", "alice"]);
+    }
+}
@@ -0,0 +1,109 @@
+use std::io::BufRead;
+
+use crate::rucksack::{CompartmentLayout, ItemAlphabet, ItemMask, Rucksack, RucksackError};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct ItemStatistics {
+    pub item: char,
+    pub priority: u32,
+    /// Number of rucksacks that contain the item at least once.
+    pub rucksacks: usize,
+    /// Number of rucksacks that have the item in enough compartments to count as misplaced.
+    pub misplaced: usize,
+    /// Number of groups that share the item.
+    pub badges: usize,
+}
+
+impl ItemStatistics {
+    pub fn misplaced_priority(&self) -> u32 {
+        self.misplaced as u32 * self.priority
+    }
+
+    pub fn badge_priority(&self) -> u32 {
+        self.badges as u32 * self.priority
+    }
+}
+
+/// Collects statistics for every item type of the alphabet in a single pass. Every shared item
+/// is counted, even if a rucksack or group shares more than one.
+pub fn collect_statistics<R: BufRead>(input: R, layout: CompartmentLayout, alphabet: &ItemAlphabet, group_size: usize) -> Result<Vec<ItemStatistics>, RucksackError> {
+    let mut statistics = alphabet.table()
+        .map(|(item, priority)| ItemStatistics { item, priority, rucksacks: 0, misplaced: 0, badges: 0 })
+        .collect::<Vec<ItemStatistics>>();
+    let mut common = ItemMask::all();
+    let mut line_number = 0;
+    for line in input.lines() {
+        let line = line.expect("Cannot read input");
+        let line = line.trim();
+        line_number += 1;
+        let misplaced = Rucksack::parse_with_compartments(line, line_number, layout.count, alphabet)?
+            .items_shared_by(layout.shared_by);
+        let items = alphabet.mask(line).unwrap();
+        items.indices().for_each(|it| statistics[it].rucksacks += 1);
+        misplaced.indices().for_each(|it| statistics[it].misplaced += 1);
+        common = common.intersect(items);
+        if line_number.is_multiple_of(group_size) {
+            common.indices().for_each(|it| statistics[it].badges += 1);
+            common = ItemMask::all();
+        }
+    }
+    if !line_number.is_multiple_of(group_size) {
+        common.indices().for_each(|it| statistics[it].badges += 1);
+    }
+    Ok(statistics)
+}
+
+pub fn to_csv(statistics: &[ItemStatistics]) -> String {
+    let mut csv = "item,priority,rucksacks,misplaced,badges,misplaced_priority,badge_priority\n".to_string();
+    for it in statistics {
+        let item = match it.item {
+            '"' => "\"\"\"\"".to_string(),
+            ',' | '\n' => format!("\"{}\"", it.item),
+            item => item.to_string(),
+        };
+        csv += &format!("{item},{},{},{},{},{},{}\n", it.priority, it.rucksacks, it.misplaced, it.badges, it.misplaced_priority(), it.badge_priority());
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"vJrwpWtwJgWrhcsFMMfFFhFp
+        jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+        PmmdzqPrVvPwwTWBwg
+        wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+        ttgJtRGJQctTZtZT
+        CrZsJsPPZsGzwwsLwLmpwMDw"#;
+
+    #[test]
+    fn collects_statistics_per_item_type() {
+        // given: the example rucksacks
+        let alphabet = ItemAlphabet::default();
+
+        // when: the statistics are collected
+        let result = collect_statistics(EXAMPLE.as_bytes(), CompartmentLayout::default(), &alphabet, 3).unwrap();
+
+        // then: the counts add up to the puzzle totals
+        let p = &result[alphabet.table().position(|(item, _)| item == 'p').unwrap()];
+        assert_eq!((p.rucksacks, p.misplaced, p.badges), (2, 1, 0));
+        assert_eq!(result.iter().map(|it| it.misplaced_priority()).sum::<u32>(), 157);
+        assert_eq!(result.iter().map(|it| it.badge_priority()).sum::<u32>(), 70);
+    }
+
+    #[test]
+    fn exports_statistics_as_csv() {
+        // given: statistics for two item types
+        let statistics = vec![
+            ItemStatistics { item: 'a', priority: 1, rucksacks: 3, misplaced: 2, badges: 1 },
+            ItemStatistics { item: ',', priority: 7, rucksacks: 1, misplaced: 1, badges: 0 },
+        ];
+
+        // when: they are exported
+        let result = to_csv(&statistics);
+
+        // then: we get one row per item type with the priority contributions
+        assert_eq!(result, "item,priority,rucksacks,misplaced,badges,misplaced_priority,badge_priority\na,1,3,2,1,2,1\n\",\",7,1,1,0,7,0\n");
+    }
+}
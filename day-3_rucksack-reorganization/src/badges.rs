@@ -0,0 +1,128 @@
+use crate::rucksack::ItemMask;
+
+/// Outcome of partitioning unordered rucksacks into groups. Groups hold indices into the input.
+#[derive(Debug, PartialEq)]
+pub enum GroupInference {
+    Unique(Vec<Vec<usize>>),
+    /// Two different partitions that both work; there may be more.
+    Ambiguous(Vec<Vec<usize>>, Vec<Vec<usize>>),
+    Infeasible,
+}
+
+/// Partitions the rucksacks into groups of `group_size` where every group shares exactly one
+/// item type. The search backtracks over all partitions, so the worst case is exponential; it
+/// stops as soon as a second partition is found.
+pub fn infer_groups(rucksacks: &[ItemMask], group_size: usize) -> GroupInference {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return GroupInference::Infeasible;
+    }
+    let search = Search {
+        masks: rucksacks.to_vec(),
+        group_size,
+    };
+    let mut assigned = vec![false; rucksacks.len()];
+    let mut solutions = vec![];
+    search.partition(&mut assigned, &mut vec![], &mut solutions);
+
+    let mut solutions = solutions.into_iter();
+    match (solutions.next(), solutions.next()) {
+        (Some(first), Some(second)) => GroupInference::Ambiguous(first, second),
+        (Some(first), None) => GroupInference::Unique(first),
+        _ => GroupInference::Infeasible,
+    }
+}
+
+struct Search {
+    masks: Vec<ItemMask>,
+    group_size: usize,
+}
+
+impl Search {
+    fn partition(&self, assigned: &mut [bool], groups: &mut Vec<Vec<usize>>, solutions: &mut Vec<Vec<Vec<usize>>>) {
+        if solutions.len() >= 2 {
+            return;
+        }
+        // the first free rucksack has to go somewhere, so only groups containing it are tried
+        let Some(first) = assigned.iter().position(|it| !it) else {
+            solutions.push(groups.clone());
+            return;
+        };
+        assigned[first] = true;
+        let mut group = vec![first];
+        self.complete_group(first + 1, self.masks[first], &mut group, assigned, groups, solutions);
+        assigned[first] = false;
+    }
+
+    fn complete_group(&self, start: usize, common: ItemMask, group: &mut Vec<usize>, assigned: &mut [bool],
+                      groups: &mut Vec<Vec<usize>>, solutions: &mut Vec<Vec<Vec<usize>>>) {
+        if group.len() == self.group_size {
+            if common.len() == 1 {
+                groups.push(group.clone());
+                self.partition(assigned, groups, solutions);
+                groups.pop();
+            }
+            return;
+        }
+        for candidate in start..self.masks.len() {
+            let common = common.intersect(self.masks[candidate]);
+            if assigned[candidate] || common.is_empty() {
+                continue;
+            }
+            assigned[candidate] = true;
+            group.push(candidate);
+            self.complete_group(candidate + 1, common, group, assigned, groups, solutions);
+            group.pop();
+            assigned[candidate] = false;
+            if solutions.len() >= 2 {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rucksack::ItemAlphabet;
+
+    fn masks<const N: usize>(rucksacks: [&str; N]) -> [ItemMask; N] {
+        rucksacks.map(|it| ItemAlphabet::default().mask(it).unwrap())
+    }
+
+    #[test]
+    fn infers_unique_groups_from_shuffled_rucksacks() {
+        // given: two groups whose rucksacks are interleaved
+        let rucksacks = masks(["aBc", "dEf", "aGh", "dIj", "aKl", "dMn"]);
+
+        // when: the groups are inferred
+        let result = infer_groups(&rucksacks, 3);
+
+        // then: the only partition groups the rucksacks by their badge
+        assert_eq!(result, GroupInference::Unique(vec![vec![0, 2, 4], vec![1, 3, 5]]));
+    }
+
+    #[test]
+    fn reports_ambiguous_partitions() {
+        // given: rucksacks that all share the same single item
+        let rucksacks = masks(["ab", "ac", "ad", "ae", "af", "ag"]);
+
+        // when: the groups are inferred
+        let result = infer_groups(&rucksacks, 3);
+
+        // then: more than one partition is possible
+        assert!(matches!(result, GroupInference::Ambiguous(first, second) if first != second));
+    }
+
+    #[test]
+    fn reports_infeasible_partitions() {
+        // given: rucksacks without a shared item and a list that cannot be split evenly
+        let without_badge = masks(["ab", "cd", "ef"]);
+        let uneven = masks(["ab", "ac"]);
+
+        // when: the groups are inferred
+        let result = (infer_groups(&without_badge, 3), infer_groups(&uneven, 3));
+
+        // then: no partition exists
+        assert_eq!(result, (GroupInference::Infeasible, GroupInference::Infeasible));
+    }
+}
@@ -1,60 +1,210 @@
-use std::ops::Index;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+use crate::analytics::{collect_statistics, to_csv};
+use crate::badges::{infer_groups, GroupInference};
+use crate::planner::plan_group;
+use crate::rucksack::{find_unknown_item, CommonItemError, CommonItemPolicy, CompartmentLayout, ItemAlphabet, ItemMask, Rucksack, RucksackError};
+
+mod analytics;
+mod badges;
+mod planner;
+mod rucksack;
 
 fn main() {
-    let input = std::fs::read_to_string("input.txt").unwrap();
-    let score = calculate_sum_of_errornous_items(input.clone());
-    println!("The total sum of errornous items is {score}");
+    let args = std::env::args().collect::<Vec<String>>();
+    let policy = match args.iter().position(|it| it == "--policy") {
+        Some(index) => args.get(index + 1).and_then(|it| CommonItemPolicy::from_str(it))
+            .expect("--policy must be one of error, sum, first"),
+        None => CommonItemPolicy::FirstOnly,
+    };
+    let alphabet = match args.iter().position(|it| it == "--alphabet") {
+        Some(index) => ItemAlphabet::from_file(args.get(index + 1).expect("--alphabet needs a path")).unwrap(),
+        None => ItemAlphabet::default(),
+    };
+    let count = match args.iter().position(|it| it == "--compartments") {
+        Some(index) => args.get(index + 1).and_then(|it| it.parse::<usize>().ok()).filter(|it| *it > 0)
+            .expect("--compartments needs a positive number"),
+        None => 2,
+    };
+    let shared_by = match args.iter().position(|it| it == "--shared-by") {
+        Some(index) => args.get(index + 1).and_then(|it| it.parse::<usize>().ok()).filter(|it| (1..=count).contains(it))
+            .expect("--shared-by needs a number between 1 and the number of compartments"),
+        None => count,
+    };
+    let layout = CompartmentLayout { count, shared_by };
+
+    let (score, score_round_two) = calculate_sums(BufReader::new(File::open("input.txt").unwrap()), policy, layout, &alphabet);
+    match score {
+        Ok(score) => println!("The total sum of errornous items is {score}"),
+        Err(error) => eprintln!("Cannot sum errornous items: {error}"),
+    }
+
+    match score_round_two {
+        Ok(score_round_two) => println!("The total sum of sticker values is {score_round_two}"),
+        Err(error) => eprintln!("Cannot sum sticker values: {error}"),
+    }
+
+    if let Some(index) = args.iter().position(|it| it == "--report") {
+        let path = args.get(index + 1).expect("--report needs a path");
+        match collect_statistics(BufReader::new(File::open("input.txt").unwrap()), layout, &alphabet, 3) {
+            Ok(statistics) => {
+                std::fs::write(path, to_csv(&statistics)).unwrap();
+                println!("Wrote item statistics to {path}");
+            }
+            Err(error) => eprintln!("Cannot collect item statistics: {error}"),
+        }
+    }
 
-    let score_round_two = calculate_sum_of_sticker_values(input);
-    println!("The total sum of sticker values is {score_round_two}");
+    if args.iter().any(|it| it == "--infer-groups") {
+        let input = std::fs::read_to_string("input.txt").unwrap();
+        let Some(rucksacks) = input.lines().map(|it| alphabet.mask(it.trim())).collect::<Option<Vec<ItemMask>>>() else {
+            eprintln!("The rucksacks contain unknown item types");
+            return;
+        };
+        match infer_groups(&rucksacks, 3) {
+            GroupInference::Unique(groups) => groups.iter().for_each(|it| println!("Group: {it:?}")),
+            GroupInference::Ambiguous(first, second) => {
+                let (a, b) = first.iter().zip(second.iter()).find(|(a, b)| a != b).unwrap();
+                println!("The groups are ambiguous, e.g. {a:?} or {b:?}");
+            }
+            GroupInference::Infeasible => println!("The rucksacks cannot be grouped by a single badge"),
+        }
+    }
+
+    if args.iter().any(|it| it == "--plan") {
+        let input = std::fs::read_to_string("input.txt").unwrap();
+        let mut total = 0;
+        for (index, group) in groups_of_n(input.lines(), 3).enumerate() {
+            match plan_group(&group, index * 3 + 1, layout, &alphabet) {
+                Ok(plan) => {
+                    plan.moves.iter().for_each(|it| println!("Line {}: move {} x {} from compartment {} to {}",
+                        index * 3 + it.rucksack + 1, it.count, it.item, it.from + 1, it.to + 1));
+                    println!("Group {}: {} moves with a cost of {}", index + 1, plan.moves.len(), plan.cost);
+                    total += plan.cost;
+                }
+                Err(error @ RucksackError::UnplannableLayout { .. }) => {
+                    eprintln!("Cannot plan the reorganization: {error}");
+                    return;
+                }
+                Err(error) => eprintln!("Cannot plan group {}: {error}", index + 1),
+            }
+        }
+        println!("The total cost of reorganizing is {total}");
+    }
 }
 
-fn calculate_sum_of_sticker_values(input: String) -> i32 {
-    create_groups_of_n(input, 3).iter()
-        .map(|it| find_common_item(it))
-        .map(|it| calculate_value(it.chars().next().unwrap()))
-        .sum()
+/// Sums the priorities of the misplaced items one rucksack at a time.
+struct ErrornousItemSum<'a> {
+    policy: CommonItemPolicy,
+    layout: CompartmentLayout,
+    alphabet: &'a ItemAlphabet,
+    line: usize,
+    sum: u32,
 }
 
-fn create_groups_of_n(input: String, group_size: usize) -> Vec<Vec<String>> {
-    let mut lines = input.lines().into_iter().map(|it| it.trim());
-    let mut groups: Vec<Vec<String>> = vec![vec![]];
-    while let Some(line) = lines.next() {
-        if groups.last().unwrap().len() == group_size {
-            groups.push(Vec::new());
-        }
-        groups.last_mut().unwrap().push(line.to_string());
+impl<'a> ErrornousItemSum<'a> {
+    fn new(policy: CommonItemPolicy, layout: CompartmentLayout, alphabet: &'a ItemAlphabet) -> Self {
+        ErrornousItemSum { policy, layout, alphabet, line: 0, sum: 0 }
+    }
+
+    fn add(&mut self, line: &str) -> Result<(), RucksackError> {
+        self.line += 1;
+        let items = Rucksack::parse_with_compartments(line, self.line, self.layout.count, self.alphabet)?
+            .items_shared_by(self.layout.shared_by);
+        self.sum += self.policy.score(items, line, self.alphabet)
+            .ok_or_else(|| RucksackError::CommonItems(CommonItemError { position: self.line, items: self.alphabet.items(items).collect() }))?;
+        Ok(())
     }
-    groups
 }
 
-fn find_common_item(input: &Vec<String>) -> String {
-    input.first().unwrap().chars().find(|c| {
-        input.iter().all(|line| line.contains(*c))
-    }).unwrap().to_string()
+/// Sums the priorities of the badges, keeping only the items shared by the current group.
+struct StickerValueSum<'a> {
+    policy: CommonItemPolicy,
+    alphabet: &'a ItemAlphabet,
+    group_size: usize,
+    line: usize,
+    /// The items shared by the rucksacks of the current group so far.
+    common: ItemMask,
+    /// The first rucksack of the current group, which decides the first shared item.
+    first: String,
+    sum: u32,
 }
 
-fn calculate_sum_of_errornous_items(input: String) -> i32 {
-    create_groups_of_n(input, 1).iter()
-        .map(|it| find_common_item(&get_compartments(it.first().unwrap().to_string())))
-        .map(|it| calculate_value(it.chars().next().unwrap()))
-        .sum()
+impl<'a> StickerValueSum<'a> {
+    fn new(policy: CommonItemPolicy, alphabet: &'a ItemAlphabet, group_size: usize) -> Self {
+        StickerValueSum { policy, alphabet, group_size, line: 0, common: ItemMask::all(), first: String::new(), sum: 0 }
+    }
+
+    fn add(&mut self, line: &str) -> Result<(), RucksackError> {
+        if self.line.is_multiple_of(self.group_size) {
+            self.common = ItemMask::all();
+            self.first.clear();
+            self.first.push_str(line);
+        }
+        self.line += 1;
+        if let Some((column, item)) = find_unknown_item(line, self.alphabet) {
+            return Err(RucksackError::UnknownItem { line: self.line, column, item });
+        }
+        self.common = self.common.intersect(self.alphabet.mask(line).unwrap());
+        if self.line.is_multiple_of(self.group_size) {
+            self.score_group()?;
+        }
+        Ok(())
+    }
+
+    /// Scores a trailing group that has fewer members than the group size.
+    fn finish(mut self) -> Result<u32, RucksackError> {
+        if !self.line.is_multiple_of(self.group_size) {
+            self.score_group()?;
+        }
+        Ok(self.sum)
+    }
+
+    fn score_group(&mut self) -> Result<(), RucksackError> {
+        let position = self.line.div_ceil(self.group_size);
+        self.sum += self.policy.score(self.common, &self.first, self.alphabet)
+            .ok_or_else(|| RucksackError::CommonItems(CommonItemError { position, items: self.alphabet.items(self.common).collect() }))?;
+        Ok(())
+    }
 }
 
-const ITEM_LIST: [char; 52] = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z'];
+/// Calculates the sum of errornous items and of sticker values in a single pass over the
+/// input, holding only the current line and the items shared by the current group.
+fn calculate_sums<R: BufRead>(input: R, policy: CommonItemPolicy, layout: CompartmentLayout, alphabet: &ItemAlphabet) -> (Result<u32, RucksackError>, Result<u32, RucksackError>) {
+    let mut errornous_items = ErrornousItemSum::new(policy, layout, alphabet);
+    let mut sticker_values = StickerValueSum::new(policy, alphabet, 3);
+    let (mut errornous_items_error, mut sticker_values_error) = (None, None);
+    for line in input.lines() {
+        let line = line.expect("Cannot read input");
+        if errornous_items_error.is_none() {
+            errornous_items_error = errornous_items.add(line.trim()).err();
+        }
+        if sticker_values_error.is_none() {
+            sticker_values_error = sticker_values.add(line.trim()).err();
+        }
+    }
 
-fn calculate_value(a: char) -> i32 {
-    ITEM_LIST.iter().position(|&r| r == a).unwrap() as i32 + 1
+    (
+        errornous_items_error.map_or(Ok(errornous_items.sum), Err),
+        sticker_values_error.map_or_else(|| sticker_values.finish(), Err),
+    )
 }
 
-fn get_compartments(line: String) -> Vec<String> {
-    let half_line = line.len() >> 1;
-    vec![line[..half_line].to_string(), line[half_line..].to_string()]
+/// Chunks the lines into groups without copying them; the last group may be smaller.
+fn groups_of_n<'a>(lines: impl Iterator<Item = &'a str>, group_size: usize) -> impl Iterator<Item = Vec<&'a str>> {
+    let mut lines = lines.map(|it| it.trim());
+    std::iter::from_fn(move || {
+        let group = lines.by_ref().take(group_size).collect::<Vec<&str>>();
+        (!group.is_empty()).then_some(group)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rucksack::split_compartments;
+    use crate::rucksack::priority;
 
     #[test]
     fn finds_correct_value_for_item_type() {
@@ -87,15 +237,15 @@ mod tests {
         ];
 
         // when: we calculate the value for each item
-        let result = items.iter().map(|(item, _)| calculate_value(*item)).collect::<Vec<i32>>();
+        let result = items.iter().map(|(item, _)| priority(*item).unwrap()).collect::<Vec<u32>>();
         // then: we get the expected value
-        assert_eq!(result, items.iter().map(|(_, value)| *value).collect::<Vec<i32>>());
+        assert_eq!(result, items.iter().map(|(_, value)| *value).collect::<Vec<u32>>());
     }
 
     #[test]
     fn creates_two_compartments_of_same_size() {
         // given: a rucksack
-        let input = vec![
+        let input = [
             ("vJrwpWtwJgWrhcsFMMfFFhFp", "vJrwpWtwJgWr", "hcsFMMfFFhFp"),
             ("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", "jqHRNqRjqzjGDLGL", "rsFMfFZSrLrFZsSL"),
             ("PmmdzqPrVvPwwTWBwg", "PmmdzqPrV", "vPwwTWBwg"),
@@ -104,8 +254,8 @@ mod tests {
             ("CrZsJsPPZsGzwwsLwLmpwMDw", "CrZsJsPPZsGz", "wwsLwLmpwMDw"),
         ];
 
-        // when: get_compartments is invoked
-        let result = input.iter().map(|line| get_compartments(line.0.to_string())).collect::<Vec<Vec<String>>>();
+        // when: split_compartments is invoked
+        let result = input.iter().map(|line| split_compartments(line.0, 2)).collect::<Vec<Vec<String>>>();
 
         // then: two compartments are returned
         assert_eq!(result, input.iter().map(|line| vec![line.1.to_string(), line.2.to_string()]).collect::<Vec<Vec<String>>>());
@@ -114,13 +264,14 @@ mod tests {
     #[test]
     fn finds_errornous_item_in_rucksack() {
         // given: a rucksack
-        let input = get_compartments("vJrwpWtwJgWrhcsFMMfFFhFp".to_string());
+        let alphabet = ItemAlphabet::default();
+        let input = Rucksack::parse_with_compartments("vJrwpWtwJgWrhcsFMMfFFhFp", 1, 2, &alphabet).unwrap();
 
         // when: we invoke the function to find the errornous item
-        let result = find_common_item(&input);
+        let result = alphabet.items(input.items_shared_by(2)).collect::<Vec<char>>();
 
         // then: we get the errornous item
-        assert_eq!(result, "p".to_string());
+        assert_eq!(result, vec!['p']);
     }
 
     #[test]
@@ -134,10 +285,10 @@ mod tests {
             CrZsJsPPZsGzwwsLwLmpwMDw"#.to_string();
 
         // when: we process the input
-        let result = calculate_sum_of_errornous_items(input);
+        let result = calculate_sums(input.as_bytes(), CommonItemPolicy::Error, CompartmentLayout::default(), &ItemAlphabet::default()).0;
 
         // then: we get the correct sum
-        assert_eq!(result, 157);
+        assert_eq!(result, Ok(157));
     }
 
     #[test]
@@ -151,7 +302,7 @@ mod tests {
             CrZsJsPPZsGzwwsLwLmpwMDw"#.to_string();
 
         // when: we group the rucksäcke into groups of three
-        let result = create_groups_of_n(input, 3);
+        let result = groups_of_n(input.lines(), 3).collect::<Vec<Vec<&str>>>();
 
         // then: we get two groups
         assert_eq!(result.len(), 2);
@@ -160,17 +311,18 @@ mod tests {
     #[test]
     fn finds_common_item_in_group() {
         // given: a group of three rucksäcke
-        let input = vec![
-            "vJrwpWtwJgWrhcsFMMfFFhFp".to_string(),
-            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL".to_string(),
-            "PmmdzqPrVvPwwTWBwg".to_string(),
-        ];
+        let input = r#"vJrwpWtwJgWrhcsFMMfFFhFp
+            jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+            PmmdzqPrVvPwwTWBwg"#;
+        let alphabet = ItemAlphabet::default();
+        let mut sticker_values = StickerValueSum::new(CommonItemPolicy::Error, &alphabet, 3);
 
         // when: we search the common item
-        let result = find_common_item(&input);
+        input.lines().for_each(|it| sticker_values.add(it.trim()).unwrap());
+        let result = alphabet.items(sticker_values.common).collect::<Vec<char>>();
 
         // then: we get the common item
-        assert_eq!(result, "r".to_string());
+        assert_eq!(result, vec!['r']);
     }
 
     #[test]
@@ -184,9 +336,112 @@ mod tests {
             CrZsJsPPZsGzwwsLwLmpwMDw"#.to_string();
 
         // when: we calculate the sum of the sticker values
-        let result = calculate_sum_of_sticker_values(input);
+        let result = calculate_sums(input.as_bytes(), CommonItemPolicy::Error, CompartmentLayout::default(), &ItemAlphabet::default()).1;
 
         // then: we get the correct sum
-        assert_eq!(result, 70);
+        assert_eq!(result, Ok(70));
+    }
+
+    #[test]
+    fn finds_all_items_shared_by_group() {
+        // given: a group sharing two item types
+        let input = "abcX\nbXyz\nQbXq";
+        let alphabet = ItemAlphabet::default();
+        let mut sticker_values = StickerValueSum::new(CommonItemPolicy::SumAll, &alphabet, 3);
+
+        // when: we search the common items
+        input.lines().for_each(|it| sticker_values.add(it).unwrap());
+        let result = alphabet.items(sticker_values.common).collect::<Vec<char>>();
+
+        // then: we get both items
+        assert_eq!(result, vec!['b', 'X']);
+    }
+
+    #[test]
+    fn applies_policy_to_rucksacks_without_single_misplaced_item() {
+        // given: rucksacks with one, two and no misplaced items
+        let input = r#"abca
+            abXabX
+            abcd"#.to_string();
+
+        // when: we process the input with every policy
+        let error = calculate_sums(input.as_bytes(), CommonItemPolicy::Error, CompartmentLayout::default(), &ItemAlphabet::default()).0;
+        let sum = calculate_sums(input.as_bytes(), CommonItemPolicy::SumAll, CompartmentLayout::default(), &ItemAlphabet::default()).0;
+        let first = calculate_sums(input.as_bytes(), CommonItemPolicy::FirstOnly, CompartmentLayout::default(), &ItemAlphabet::default()).0;
+
+        // then: the second rucksack is reported or scored according to the policy
+        assert_eq!(error, Err(RucksackError::CommonItems(CommonItemError { position: 2, items: vec!['a', 'b', 'X'] })));
+        assert_eq!(sum, Ok(1 + 1 + 2 + 50));
+        assert_eq!(first, Ok(1 + 1));
+    }
+
+    #[test]
+    fn splits_multi_byte_items_into_equal_compartments() {
+        // given: a rucksack with multi-byte items
+        let input = "äöüäxy";
+
+        // when: split_compartments is invoked
+        let result = split_compartments(input, 2);
+
+        // then: both compartments hold three items
+        assert_eq!(result, vec!["äöü".to_string(), "äxy".to_string()]);
+    }
+
+    #[test]
+    fn reports_invalid_rucksacks_instead_of_panicking() {
+        // given: an odd-length rucksack and a group with an unknown item
+        let odd = "abcab".to_string();
+        let unknown = r#"abca
+            ab1b
+            abcb"#.to_string();
+
+        // when: we process the input
+        let errornous = calculate_sums(odd.as_bytes(), CommonItemPolicy::FirstOnly, CompartmentLayout::default(), &ItemAlphabet::default()).0;
+        let stickers = calculate_sums(unknown.as_bytes(), CommonItemPolicy::FirstOnly, CompartmentLayout::default(), &ItemAlphabet::default()).1;
+
+        // then: the offending lines are reported
+        assert_eq!(errornous, Err(RucksackError::UnevenLength { line: 1, length: 5, compartments: 2 }));
+        assert_eq!(stickers, Err(RucksackError::UnknownItem { line: 2, column: 3, item: '1' }));
+    }
+
+    #[test]
+    fn splits_into_configurable_number_of_compartments() {
+        // given: a rucksack with nine items
+        let input = "abcdefghi";
+
+        // when: it is split into three compartments
+        let result = split_compartments(input, 3);
+
+        // then: each compartment holds three items
+        assert_eq!(result, vec!["abc".to_string(), "def".to_string(), "ghi".to_string()]);
+    }
+
+    #[test]
+    fn calculates_sum_of_items_shared_by_some_compartments() {
+        // given: rucksacks with three compartments
+        let input = r#"abcaxyazb
+            xyzdxyefg"#;
+        let layout = CompartmentLayout { count: 3, shared_by: 2 };
+
+        // when: we sum the items shared by at least two compartments
+        let result = calculate_sums(input.as_bytes(), CommonItemPolicy::SumAll, layout, &ItemAlphabet::default()).0;
+
+        // then: a and b of the first and x and y of the second rucksack are counted
+        assert_eq!(result, Ok(1 + 2 + 24 + 25));
+    }
+
+    #[test]
+    fn scores_the_shared_item_that_comes_first_in_the_line() {
+        // given: a rucksack sharing b and a, with b packed first, and a group whose first rucksack starts with B
+        let rucksack = "baab";
+        let group = "Bxa\naBx\nxaB";
+
+        // when: only the first shared item is scored
+        let errornous = calculate_sums(rucksack.as_bytes(), CommonItemPolicy::FirstOnly, CompartmentLayout::default(), &ItemAlphabet::default()).0;
+        let stickers = calculate_sums(group.as_bytes(), CommonItemPolicy::FirstOnly, CompartmentLayout::default(), &ItemAlphabet::default()).1;
+
+        // then: the line order decides instead of the alphabet order
+        assert_eq!(errornous, Ok(2));
+        assert_eq!(stickers, Ok(28));
     }
 }
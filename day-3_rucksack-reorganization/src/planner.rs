@@ -0,0 +1,124 @@
+use crate::rucksack::{CompartmentLayout, ItemAlphabet, Rucksack, RucksackError};
+
+#[derive(Debug, PartialEq)]
+pub struct Move {
+    /// Position of the rucksack within the planned lines, starting at 0.
+    pub rucksack: usize,
+    pub item: char,
+    pub count: usize,
+    /// Compartments are numbered from 0.
+    pub from: usize,
+    pub to: usize,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Plan {
+    pub moves: Vec<Move>,
+    /// Sum of the priorities of all moved items.
+    pub cost: u32,
+}
+
+/// Plans the fewest moves so that no item type is packed into `shared_by` compartments of any
+/// of the rucksacks. For every misplaced item type the compartments holding the most copies
+/// keep theirs and the copies of all other compartments are moved to the one holding the most;
+/// on a tie the first compartment is preferred. Compartments may end up with different sizes.
+/// A layout where a single compartment makes an item misplaced cannot be planned.
+pub fn plan_group(lines: &[&str], first_line: usize, layout: CompartmentLayout, alphabet: &ItemAlphabet) -> Result<Plan, RucksackError> {
+    if layout.shared_by < 2 {
+        return Err(RucksackError::UnplannableLayout { shared_by: layout.shared_by });
+    }
+    let mut plan = Plan::default();
+    for (rucksack, line) in lines.iter().enumerate() {
+        let parsed = Rucksack::parse_with_compartments(line, first_line + rucksack, layout.count, alphabet)?;
+        for item in alphabet.items(parsed.items_shared_by(layout.shared_by)) {
+            let counts = parsed.contents.iter().map(|it| it.chars().filter(|c| *c == item).count()).collect::<Vec<usize>>();
+            let mut by_count = (0..counts.len()).collect::<Vec<usize>>();
+            by_count.sort_by_key(|it| std::cmp::Reverse(counts[*it]));
+            let to = by_count[0];
+            let mut from = by_count[layout.shared_by - 1..].iter().copied().filter(|it| counts[*it] > 0).collect::<Vec<usize>>();
+            from.sort();
+            for from in from {
+                plan.cost += counts[from] as u32 * alphabet.priority(item).unwrap();
+                plan.moves.push(Move { rucksack, item, count: counts[from], from, to });
+            }
+        }
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_fewer_copies_of_shared_item() {
+        // given: a rucksack with one p on the left and two on the right
+        let lines = ["abcpdppe"];
+
+        // when: the reorganization is planned
+        let result = plan_group(&lines, 1, CompartmentLayout::default(), &ItemAlphabet::default()).unwrap();
+
+        // then: the single p is moved to the right
+        assert_eq!(result.moves, vec![Move { rucksack: 0, item: 'p', count: 1, from: 0, to: 1 }]);
+        assert_eq!(result.cost, 16);
+    }
+
+    #[test]
+    fn plans_moves_across_a_group() {
+        // given: a group with two shared item types in one rucksack and none in another
+        let lines = ["abcBbb", "abcdef", "XxxX"];
+
+        // when: the reorganization is planned
+        let result = plan_group(&lines, 1, CompartmentLayout::default(), &ItemAlphabet::default()).unwrap();
+
+        // then: every shared item type is moved and the cost adds up
+        assert_eq!(result.moves, vec![
+            Move { rucksack: 0, item: 'b', count: 1, from: 0, to: 1 },
+            Move { rucksack: 2, item: 'x', count: 1, from: 1, to: 0 },
+            Move { rucksack: 2, item: 'X', count: 1, from: 1, to: 0 },
+        ]);
+        assert_eq!(result.cost, 2 + 24 + 50);
+    }
+
+    #[test]
+    fn reports_invalid_rucksack_in_group() {
+        // given: a group with an odd-length rucksack
+        let lines = ["abab", "abc"];
+
+        // when: the reorganization is planned
+        let result = plan_group(&lines, 4, CompartmentLayout::default(), &ItemAlphabet::default());
+
+        // then: the line is reported
+        assert_eq!(result, Err(RucksackError::UnevenLength { line: 5, length: 3, compartments: 2 }));
+    }
+
+    #[test]
+    fn rejects_layout_misplacing_items_in_a_single_compartment() {
+        // given: a layout where every item shared by one compartment is misplaced
+        let layout = CompartmentLayout { count: 2, shared_by: 1 };
+
+        // when: the reorganization is planned
+        let result = plan_group(&["abab"], 1, layout, &ItemAlphabet::default());
+
+        // then: the layout is rejected instead of panicking
+        assert_eq!(result, Err(RucksackError::UnplannableLayout { shared_by: 1 }));
+    }
+
+    #[test]
+    fn plans_moves_for_more_compartments() {
+        // given: three compartments with a in all of them and b in two
+        let lines = ["aabaxyabz"];
+        let layout = CompartmentLayout { count: 3, shared_by: 2 };
+
+        // when: the reorganization is planned
+        let result = plan_group(&lines, 1, layout, &ItemAlphabet::default()).unwrap();
+
+        // then: the other copies of a and b are moved to the compartment holding the most
+        assert_eq!(result.moves, vec![
+            Move { rucksack: 0, item: 'a', count: 1, from: 1, to: 0 },
+            Move { rucksack: 0, item: 'a', count: 1, from: 2, to: 0 },
+            Move { rucksack: 0, item: 'b', count: 1, from: 2, to: 0 },
+        ]);
+        assert_eq!(result.cost, 1 + 1 + 2);
+    }
+}
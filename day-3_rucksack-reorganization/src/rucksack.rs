@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Priority of an item type: `a` to `z` are 1 to 26, `A` to `Z` are 27 to 52.
+pub fn priority(item: char) -> Option<u32> {
+    match item {
+        'a'..='z' => Some(item as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(item as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+/// Item types that may be packed, each with its priority. Item types are numbered by their
+/// position, so an alphabet holds at most 64 of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemAlphabet {
+    items: Vec<char>,
+    priorities: Vec<u32>,
+    /// Position of every item type, so items are looked up in constant time.
+    indices: HashMap<char, usize>,
+}
+
+impl Default for ItemAlphabet {
+    fn default() -> Self {
+        let table = ('a'..='z').chain('A'..='Z').map(|it| (it, priority(it).unwrap())).collect::<Vec<(char, u32)>>();
+        ItemAlphabet::new(&table).unwrap()
+    }
+}
+
+impl ItemAlphabet {
+    pub fn new(table: &[(char, u32)]) -> Result<Self, String> {
+        if table.len() > 64 {
+            return Err(format!("An alphabet holds at most 64 item types, got {}", table.len()));
+        }
+        let mut indices = HashMap::new();
+        for (index, (item, _)) in table.iter().enumerate() {
+            if indices.insert(*item, index).is_some() {
+                return Err(format!("Item type `{item}` is listed twice"));
+            }
+        }
+        Ok(ItemAlphabet { items: table.iter().map(|it| it.0).collect(), priorities: table.iter().map(|it| it.1).collect(), indices })
+    }
+
+    /// Reads a priority table with one `<item> <priority>` pair per line. Lines starting with `#`
+    /// are ignored.
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let input = std::fs::read_to_string(path).map_err(|err| format!("Cannot read {path}: {err}"))?;
+        ItemAlphabet::parse(&input)
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let table = input.lines().enumerate()
+            .map(|(index, it)| (index + 1, it.trim()))
+            .filter(|(_, it)| !it.is_empty() && !it.starts_with('#'))
+            .map(|(index, line)| {
+                let mut parts = line.split_whitespace();
+                let mut item = parts.next().unwrap().chars();
+                let (Some(item), None) = (item.next(), item.next()) else {
+                    return Err(format!("Line {index}: expected a single character item type in `{line}`"));
+                };
+                let priority = parts.next().and_then(|it| it.parse::<u32>().ok())
+                    .ok_or(format!("Line {index}: expected a priority for `{item}`"))?;
+                Ok((item, priority))
+            })
+            .collect::<Result<Vec<(char, u32)>, String>>()?;
+        ItemAlphabet::new(&table)
+    }
+
+    /// Item types with their priorities in alphabet order.
+    pub fn table(&self) -> impl Iterator<Item = (char, u32)> + '_ {
+        self.items.iter().copied().zip(self.priorities.iter().copied())
+    }
+
+    /// Position of the item type in the alphabet.
+    pub fn index(&self, item: char) -> Option<usize> {
+        self.indices.get(&item).copied()
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        self.indices.contains_key(&item)
+    }
+
+    pub fn priority(&self, item: char) -> Option<u32> {
+        self.index(item).map(|index| self.priorities[index])
+    }
+
+    /// Returns `None` if any of the items is not part of the alphabet.
+    pub fn mask(&self, items: &str) -> Option<ItemMask> {
+        items.chars().try_fold(ItemMask::default(), |mask, item| self.index(item).map(|index| ItemMask(mask.0 | 1 << index)))
+    }
+
+    pub fn items(&self, mask: ItemMask) -> impl Iterator<Item = char> + '_ {
+        mask.indices().map(|it| self.items[it])
+    }
+
+    pub fn priorities(&self, mask: ItemMask) -> impl Iterator<Item = u32> + '_ {
+        mask.indices().map(|it| self.priorities[it])
+    }
+}
+
+/// Set of item types where bit `n` is set when the `n`th item type of the alphabet is present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ItemMask(pub u64);
+
+impl ItemMask {
+    pub fn all() -> Self {
+        ItemMask(!0)
+    }
+
+    pub fn intersect(self, other: ItemMask) -> Self {
+        ItemMask(self.0 & other.0)
+    }
+
+    pub fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.0 & 1 << index != 0
+    }
+
+    /// Positions of the contained item types in the alphabet in ascending order.
+    pub fn indices(&self) -> impl Iterator<Item = usize> {
+        let mask = self.0;
+        (0..64).filter(move |it| mask & 1 << it != 0)
+    }
+}
+
+/// What to do when a rucksack or group does not share exactly one item type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommonItemPolicy {
+    Error,
+    SumAll,
+    /// Scores only the shared item that comes first in the line.
+    FirstOnly,
+}
+
+impl CommonItemPolicy {
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "error" => Some(CommonItemPolicy::Error),
+            "sum" => Some(CommonItemPolicy::SumAll),
+            "first" => Some(CommonItemPolicy::FirstOnly),
+            _ => None,
+        }
+    }
+
+    /// Priority contributed by the shared items, `None` if the policy rejects them. `line` holds
+    /// the items in the order they were packed, which decides the first shared item.
+    pub fn score(&self, items: ItemMask, line: &str, alphabet: &ItemAlphabet) -> Option<u32> {
+        match self {
+            CommonItemPolicy::Error if items.len() != 1 => None,
+            CommonItemPolicy::SumAll => Some(alphabet.priorities(items).sum()),
+            _ => Some(line.chars().find(|it| alphabet.index(*it).is_some_and(|index| items.contains(index)))
+                .and_then(|it| alphabet.priority(it))
+                .unwrap_or(0)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CommonItemError {
+    /// 1-based index of the rucksack or group.
+    pub position: usize,
+    pub items: Vec<char>,
+}
+
+impl fmt::Display for CommonItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} shares {} item types instead of one: {:?}", self.position, self.items.len(), self.items)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RucksackError {
+    UnevenLength { line: usize, length: usize, compartments: usize },
+    UnknownItem { line: usize, column: usize, item: char },
+    CommonItems(CommonItemError),
+    /// Items that are misplaced in a single compartment cannot be moved anywhere.
+    UnplannableLayout { shared_by: usize },
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RucksackError::UnevenLength { line, length, compartments } =>
+                write!(f, "Line {line}: {length} items cannot be split into {compartments} equal compartments"),
+            RucksackError::UnknownItem { line, column, item } =>
+                write!(f, "Line {line}, column {column}: `{item}` is not a known item type"),
+            RucksackError::CommonItems(error) => write!(f, "{error}"),
+            RucksackError::UnplannableLayout { shared_by } =>
+                write!(f, "Items misplaced in {shared_by} of the compartments cannot be reorganized, at least 2 are needed"),
+        }
+    }
+}
+
+/// Finds the first item that is not part of the alphabet, with its 1-based column.
+pub fn find_unknown_item(line: &str, alphabet: &ItemAlphabet) -> Option<(usize, char)> {
+    line.chars().enumerate().find(|(_, it)| !alphabet.contains(*it)).map(|(index, it)| (index + 1, it))
+}
+
+/// How many compartments a rucksack has and in how many of them an item type has to be
+/// packed to count as misplaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompartmentLayout {
+    pub count: usize,
+    pub shared_by: usize,
+}
+
+impl Default for CompartmentLayout {
+    fn default() -> Self {
+        CompartmentLayout { count: 2, shared_by: 2 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rucksack {
+    pub compartments: Vec<ItemMask>,
+    /// The items of every compartment in the order they were packed.
+    pub contents: Vec<String>,
+}
+
+impl Rucksack {
+    /// Parses a rucksack with the given number of compartments from the line with the given
+    /// 1-based number, rejecting item counts that cannot be split evenly and unknown item types.
+    pub fn parse_with_compartments(line: &str, number: usize, compartments: usize, alphabet: &ItemAlphabet) -> Result<Self, RucksackError> {
+        let length = line.chars().count();
+        if compartments == 0 || !length.is_multiple_of(compartments) {
+            return Err(RucksackError::UnevenLength { line: number, length, compartments });
+        }
+        if let Some((column, item)) = find_unknown_item(line, alphabet) {
+            return Err(RucksackError::UnknownItem { line: number, column, item });
+        }
+        let contents = split_compartments(line, compartments);
+        Ok(Rucksack { compartments: contents.iter().map(|it| alphabet.mask(it).unwrap()).collect(), contents })
+    }
+
+    /// Item types that are packed into at least `compartments` of the compartments.
+    pub fn items_shared_by(&self, compartments: usize) -> ItemMask {
+        let mut counts = [0; 64];
+        self.compartments.iter().for_each(|it| it.indices().for_each(|index| counts[index] += 1));
+        ItemMask((0..64).filter(|it| counts[*it] >= compartments).fold(0, |mask, it| mask | 1 << it))
+    }
+}
+
+/// Splits the items into equally sized compartments, counting characters rather than bytes.
+/// Items that do not divide evenly end up in the last compartment.
+pub fn split_compartments(line: &str, count: usize) -> Vec<String> {
+    let size = line.chars().count() / count;
+    let mut items = line.chars();
+    let mut compartments = (1..count).map(|_| items.by_ref().take(size).collect::<String>()).collect::<Vec<String>>();
+    compartments.push(items.collect());
+    compartments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculates_priority_arithmetically() {
+        // given: items at the borders of both ranges
+        let items = ['a', 'z', 'A', 'Z', '1'];
+
+        // when: the priorities are calculated
+        let result = items.iter().map(|it| priority(*it)).collect::<Vec<Option<u32>>>();
+
+        // then: we get the puzzle priorities
+        assert_eq!(result, vec![Some(1), Some(26), Some(27), Some(52), None]);
+    }
+
+    #[test]
+    fn finds_misplaced_item_with_mask() {
+        // given: a rucksack
+        let alphabet = ItemAlphabet::default();
+        let rucksack = Rucksack::parse_with_compartments("vJrwpWtwJgWrhcsFMMfFFhFp", 1, 2, &alphabet).unwrap();
+
+        // when: the misplaced items are calculated
+        let result = rucksack.items_shared_by(2);
+
+        // then: only p is in both compartments
+        assert_eq!(alphabet.items(result).collect::<String>(), "p");
+        assert_eq!(alphabet.priorities(result).sum::<u32>(), 16);
+    }
+
+    #[test]
+    fn scores_shared_items_according_to_policy() {
+        // given: masks with none, one and two shared items
+        let alphabet = ItemAlphabet::default();
+        let lines = ["", "b", "bC"];
+
+        // when: each policy scores them
+        let result = [CommonItemPolicy::Error, CommonItemPolicy::SumAll, CommonItemPolicy::FirstOnly]
+            .map(|policy| lines.map(|it| policy.score(alphabet.mask(it).unwrap(), it, &alphabet)));
+
+        // then: only the error policy rejects anything but exactly one item
+        assert_eq!(result, [
+            [None, Some(2), None],
+            [Some(0), Some(2), Some(31)],
+            [Some(0), Some(2), Some(2)],
+        ]);
+    }
+
+    #[test]
+    fn rejects_odd_length_and_unknown_items() {
+        // given: a rucksack with an odd number of items and one with an unknown item
+        let alphabet = ItemAlphabet::default();
+        let lines = ["abcab", "abä9ab"];
+
+        // when: they are parsed
+        let result = lines.iter().enumerate().map(|(index, it)| Rucksack::parse_with_compartments(it, index + 1, 2, &alphabet)).collect::<Vec<_>>();
+
+        // then: both are explained
+        assert_eq!(result, vec![
+            Err(RucksackError::UnevenLength { line: 1, length: 5, compartments: 2 }),
+            Err(RucksackError::UnknownItem { line: 2, column: 3, item: 'ä' }),
+        ]);
+        assert_eq!(result[1].as_ref().unwrap_err().to_string(), "Line 2, column 3: `ä` is not a known item type");
+    }
+
+    #[test]
+    fn uses_custom_priority_table() {
+        // given: an alphabet with non-ASCII items
+        let alphabet = ItemAlphabet::parse(r#"# custom items
+            ä 100
+            ö 200
+            x 1"#).unwrap();
+
+        // when: a rucksack with multi-byte items is parsed
+        let result = Rucksack::parse_with_compartments("äxöä", 1, 2, &alphabet).unwrap().items_shared_by(2);
+
+        // then: the custom priority is used
+        assert_eq!(alphabet.items(result).collect::<String>(), "ä");
+        assert_eq!(alphabet.priorities(result).sum::<u32>(), 100);
+    }
+
+    #[test]
+    fn rejects_invalid_priority_tables() {
+        // given: tables with a duplicate item, a missing priority and a multi-character item
+        let tables = ["a 1\na 2", "a", "ab 3"];
+
+        // when: they are parsed
+        let result = tables.iter().map(|it| ItemAlphabet::parse(it)).collect::<Vec<_>>();
+
+        // then: every table is rejected
+        assert!(result.iter().all(|it| it.is_err()));
+    }
+
+    #[test]
+    fn finds_items_shared_by_some_compartments() {
+        // given: a rucksack with three compartments
+        let alphabet = ItemAlphabet::default();
+        let rucksack = Rucksack::parse_with_compartments("abcaxyazb", 1, 3, &alphabet).unwrap();
+
+        // when: items shared by two and by all three compartments are calculated
+        let result = (rucksack.items_shared_by(2), rucksack.items_shared_by(3));
+
+        // then: b is only in two compartments, a is in all of them
+        assert_eq!(alphabet.items(result.0).collect::<String>(), "ab");
+        assert_eq!(alphabet.items(result.1).collect::<String>(), "a");
+    }
+
+    #[test]
+    fn rejects_rucksack_that_cannot_be_split_evenly() {
+        // given: eight items
+        let line = "abcdefgh";
+
+        // when: they are split into three compartments
+        let result = Rucksack::parse_with_compartments(line, 4, 3, &ItemAlphabet::default());
+
+        // then: the line is rejected
+        assert_eq!(result, Err(RucksackError::UnevenLength { line: 4, length: 8, compartments: 3 }));
+    }
+
+    #[test]
+    fn rejects_rucksack_without_compartments() {
+        // given: an empty line
+        let line = "";
+
+        // when: it is split into no compartments
+        let result = Rucksack::parse_with_compartments(line, 1, 0, &ItemAlphabet::default());
+
+        // then: the line is rejected instead of dividing by zero
+        assert_eq!(result, Err(RucksackError::UnevenLength { line: 1, length: 0, compartments: 0 }));
+    }
+}
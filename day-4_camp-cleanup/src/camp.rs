@@ -0,0 +1,149 @@
+use std::ops::Range;
+
+use crate::interval_set::IntervalSet;
+use crate::section::{Section, SectionId};
+
+/// Identifies an elf by the position of its pair in the input and its position within the pair,
+/// both starting at 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elf {
+    pub pair: usize,
+    pub member: usize,
+}
+
+pub fn list_elves(pairs: &[Vec<Section>]) -> Vec<(Elf, &Section)> {
+    pairs.iter().enumerate()
+        .flat_map(|(pair, sections)| sections.iter().enumerate().map(move |(member, section)| (Elf { pair, member }, section)))
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CampCoverage {
+    /// Number of sections assigned to at least one elf.
+    pub covered: SectionId,
+    /// Sections between the lowest and the highest assigned one that nobody cleans.
+    pub gaps: Vec<Section>,
+    /// Highest number of elves assigned to the same section.
+    pub redundancy: usize,
+    /// Sections assigned to `redundancy` elves.
+    pub most_redundant: Vec<Section>,
+    /// Fewest elves that together still clean every covered section.
+    pub cover: Vec<Elf>,
+}
+
+/// Analyzes the assignments of all pairs at once. The redundancy is found with a sweep over
+/// the first and last sections and the cover is chosen greedily, which is optimal for intervals.
+pub fn analyze_camp(pairs: &[Vec<Section>]) -> CampCoverage {
+    let mut elves = list_elves(pairs);
+    let covered = IntervalSet::from_ranges(elves.iter().map(|(_, it)| it.first()..it.last() + 1));
+    let camp = match (covered.ranges().first(), covered.ranges().last()) {
+        (Some(first), Some(last)) => IntervalSet::from_ranges(std::iter::once(first.start..last.end)),
+        _ => IntervalSet::default(),
+    };
+    let (redundancy, most_redundant) = find_most_redundant(&elves);
+
+    elves.sort_by_key(|(_, it)| it.first());
+    let mut cover = vec![];
+    let mut candidates = elves.iter().peekable();
+    for range in covered.ranges() {
+        let mut next = range.start;
+        while next < range.end {
+            let mut best: Option<&(Elf, &Section)> = None;
+            while let Some(candidate) = candidates.next_if(|(_, it)| it.first() <= next) {
+                if best.is_none_or(|(_, it)| candidate.1.last() > it.last()) {
+                    best = Some(candidate);
+                }
+            }
+            // the section `next` is covered, so one of the new candidates has to reach it
+            let (elf, section) = best.unwrap();
+            cover.push(*elf);
+            next = section.last() + 1;
+        }
+    }
+
+    CampCoverage {
+        covered: covered.coverage(),
+        gaps: Section::from_interval_set(&camp.difference(&covered)),
+        redundancy,
+        most_redundant: Section::from_interval_set(&most_redundant),
+        cover,
+    }
+}
+
+fn find_most_redundant(elves: &[(Elf, &Section)]) -> (usize, IntervalSet<SectionId>) {
+    // ends sort before starts at the same position, so touching assignments are not counted twice
+    let mut events = elves.iter()
+        .flat_map(|(_, it)| [(it.first(), 1), (it.last() + 1, -1)])
+        .collect::<Vec<(SectionId, isize)>>();
+    events.sort();
+    let mut depth = 0;
+    let mut redundancy = 0;
+    let mut deepest: Vec<Range<SectionId>> = vec![];
+    for (index, (position, delta)) in events.iter().enumerate() {
+        depth += delta;
+        let Some((next, _)) = events.get(index + 1) else {
+            break;
+        };
+        if next == position || depth == 0 {
+            continue;
+        }
+        if depth as usize > redundancy {
+            redundancy = depth as usize;
+            deepest.clear();
+        }
+        if depth as usize == redundancy {
+            deepest.push(*position..*next);
+        }
+    }
+    (redundancy, IntervalSet::from_ranges(deepest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_teams;
+
+    #[test]
+    fn analyzes_the_example_camp() {
+        // given: the example assignments
+        let pairs = get_teams(r#"2-4,6-8
+            2-3,4-5
+            5-7,7-9
+            2-8,3-7
+            6-6,4-6
+            2-6,4-8"#);
+
+        // when: the camp is analyzed
+        let result = analyze_camp(&pairs);
+
+        // then: sections 2 to 9 are covered by two elves and section 6 by eight
+        assert_eq!(result, CampCoverage {
+            covered: 8,
+            gaps: vec![],
+            redundancy: 8,
+            most_redundant: vec![Section::new(6, 6)],
+            cover: vec![Elf { pair: 3, member: 0 }, Elf { pair: 2, member: 1 }],
+        });
+    }
+
+    #[test]
+    fn reports_gaps_and_covers_every_part_of_the_camp() {
+        // given: assignments with sections nobody cleans
+        let pairs = get_teams(r#"1-2,5-6
+            2-3,9-9"#);
+
+        // when: the camp is analyzed
+        let result = analyze_camp(&pairs);
+
+        // then: the gaps are reported and each covered part needs its own elves
+        assert_eq!(result.covered, 6);
+        assert_eq!(result.gaps, vec![Section::new(4, 4), Section::new(7, 8)]);
+        assert_eq!((result.redundancy, result.most_redundant), (2, vec![Section::new(2, 2)]));
+        assert_eq!(result.cover, vec![
+            Elf { pair: 0, member: 0 },
+            Elf { pair: 1, member: 0 },
+            Elf { pair: 0, member: 1 },
+            Elf { pair: 1, member: 1 },
+        ]);
+    }
+}
@@ -0,0 +1,135 @@
+use std::iter::Sum;
+use std::ops::{Range, Sub};
+
+/// A set of values stored as sorted, disjoint and non-adjacent half-open ranges.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn from_ranges<I: IntoIterator<Item = Range<T>>>(ranges: I) -> Self {
+        let mut ranges = ranges.into_iter().filter(|it| it.start < it.end).collect::<Vec<Range<T>>>();
+        ranges.sort_by_key(|it| it.start);
+        let mut merged: Vec<Range<T>> = vec![];
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        IntervalSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn union(&self, other: &IntervalSet<T>) -> Self {
+        IntervalSet::from_ranges(self.ranges.iter().chain(other.ranges.iter()).cloned())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet<T>) -> Self {
+        let mut ranges = vec![];
+        let (mut a, mut b) = (self.ranges.iter().peekable(), other.ranges.iter().peekable());
+        while let (Some(left), Some(right)) = (a.peek(), b.peek()) {
+            let start = left.start.max(right.start);
+            let end = left.end.min(right.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            if left.end < right.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet<T>) -> Self {
+        let mut ranges = vec![];
+        for range in &self.ranges {
+            let mut start = range.start;
+            for cut in other.ranges.iter().filter(|it| it.start < range.end && it.end > range.start) {
+                if cut.start > start {
+                    ranges.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    /// Whether every value of `other` is also part of this set.
+    pub fn contains_set(&self, other: &IntervalSet<T>) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    pub fn overlaps(&self, other: &IntervalSet<T>) -> bool {
+        !self.intersection(other).is_empty()
+    }
+}
+
+impl<T: Ord + Copy + Sub<Output = T> + Sum> IntervalSet<T> {
+    /// Number of values in the set.
+    pub fn coverage(&self) -> T {
+        self.ranges.iter().map(|it| it.end - it.start).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_overlapping_and_adjacent_ranges() {
+        // given: unsorted ranges that overlap or touch
+        let ranges = [6..8, 1..3, 2..4, 4..5, 9..9];
+
+        // when: a set is built from them
+        let result = IntervalSet::from_ranges(ranges);
+
+        // then: the ranges are merged and empty ranges are dropped
+        assert_eq!(result.ranges, vec![1..5, 6..8]);
+    }
+
+    #[test]
+    fn calculates_union_intersection_and_difference() {
+        // given: two sets
+        let a = IntervalSet::from_ranges([1..5, 8..12]);
+        let b = IntervalSet::from_ranges([3..9, 11..15]);
+
+        // when: the set operations are applied
+        let union = a.union(&b);
+        let intersection = a.intersection(&b);
+        let difference = a.difference(&b);
+
+        // then: we get the expected ranges
+        assert_eq!((union.ranges.len(), union.coverage()), (1, 14));
+        assert_eq!((a.coverage(), intersection.coverage()), (8, 4));
+        assert_eq!(intersection.ranges, vec![3..5, 8..9, 11..12]);
+        assert_eq!(difference.ranges, vec![1..3, 9..11]);
+    }
+
+    #[test]
+    fn answers_containment_queries() {
+        // given: a set and a subset
+        let set = IntervalSet::from_ranges([2..9, 12..14]);
+        let subset = IntervalSet::from_ranges([3..8, 12..13]);
+        let disjoint = IntervalSet::from_ranges([9..10, 10..12]);
+
+        // when / then: containment and overlap are reported
+        assert!(set.contains_set(&subset));
+        assert!(!subset.contains_set(&set));
+        assert!(set.overlaps(&subset));
+        assert!(!set.overlaps(&disjoint));
+    }
+}
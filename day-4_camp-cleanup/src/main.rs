@@ -1,37 +1,132 @@
-use std::ops::Range;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::camp::analyze_camp;
+use crate::overlap::{classify, OverlapKind};
+use crate::reassign::{reassign, Fix};
+use crate::section::Section;
+use crate::sweep::find_all_overlaps;
+use crate::team::{any_overlap, common_intersection, containing_all};
+use crate::visualize::visualize;
+
+mod camp;
+mod interval_set;
+mod overlap;
+mod reassign;
+mod section;
+mod sweep;
+mod team;
+mod visualize;
 
 fn main() {
     let input = std::fs::read_to_string("input.txt").unwrap();
-    let score = get_total_number_of_overlapping_ranges(input.clone(), true);
+    let teams = get_teams(&input);
+
+    if std::env::args().any(|it| it == "--reassign") {
+        // the corrected input goes to stdout and the suggestions to stderr, so it can be redirected into a file
+        for (index, sections) in teams.iter().enumerate() {
+            let reassignment = reassign(sections);
+            for fix in &reassignment.fixes {
+                match fix {
+                    Fix::Trim { member, to } => eprintln!("line {}: trim {} to {to}", index + 1, sections[*member]),
+                    Fix::Split { member, into } => eprintln!("line {}: split {} into {}", index + 1, sections[*member],
+                                                             into.iter().map(|it| it.to_string()).collect::<Vec<String>>().join(" and ")),
+                    Fix::Drop { member } => eprintln!("line {}: drop {}", index + 1, sections[*member]),
+                }
+            }
+            println!("{}", reassignment.to_line());
+        }
+        return;
+    }
+
+    let kinds = overlap_kinds_per_line(&teams);
+    let score = count_where(&kinds, OverlapKind::is_containment);
     println!("The total sum of fully overlapping sections is {score}");
 
-    let score_round_two = get_total_number_of_overlapping_ranges(input, false);
+    let score_round_two = count_where(&kinds, OverlapKind::shares_sections);
     println!("The total sum of partially overlapping sections is {score_round_two}");
+
+    if std::env::args().any(|it| it == "--kinds") {
+        for (kind, count) in count_overlap_kinds(&teams) {
+            println!("{kind:?}: {count}");
+        }
+    }
+
+    if std::env::args().any(|it| it == "--visualize") {
+        let args = std::env::args().collect::<Vec<String>>();
+        let width = match args.iter().position(|it| it == "--width") {
+            Some(index) => args.get(index + 1).and_then(|it| it.parse::<usize>().ok()).filter(|it| *it > 0)
+                .expect("--width needs a positive number"),
+            None => 80,
+        };
+        print!("{}", visualize(&teams, width));
+    }
+
+    if std::env::args().any(|it| it == "--camp") {
+        let camp = analyze_camp(&teams);
+        println!("{} sections are covered", camp.covered);
+        let gaps = camp.gaps.iter().map(|it| it.to_string()).collect::<Vec<String>>();
+        println!("Sections covered by nobody: {}", if gaps.is_empty() { "none".to_string() } else { gaps.join(", ") });
+        let most_redundant = camp.most_redundant.iter().map(|it| it.to_string()).collect::<Vec<String>>();
+        println!("Sections covered by {} elves: {}", camp.redundancy, most_redundant.join(", "));
+        println!("{} elves are enough to cover the camp:", camp.cover.len());
+        for elf in camp.cover {
+            println!("  line {}, elf {}: {}", elf.pair + 1, elf.member + 1, teams[elf.pair][elf.member]);
+        }
+    }
+
+    if std::env::args().any(|it| it == "--teams") {
+        let overlapping = teams.iter().filter(|it| any_overlap(it)).count();
+        println!("The number of teams where any two elves overlap is {overlapping}");
+        let contained = teams.iter().filter(|it| it.len() > 1 && containing_all(it).is_some()).count();
+        println!("The number of teams where one elf contains all others is {contained}");
+        let shared = teams.iter().filter_map(|it| common_intersection(it)).count();
+        println!("The number of teams sharing a common section is {shared}");
+    }
+
+    if std::env::args().any(|it| it == "--all-pairs") {
+        let report = find_all_overlaps(&teams);
+        println!("{} pairs of elves overlap across the camp", report.overlapping.len());
+        println!("{} pairs of elves fully contain one another across the camp", report.containing.len());
+        if std::env::args().any(|it| it == "--list") {
+            for (a, b) in report.containing {
+                println!("  line {}, elf {} ({}) contains line {}, elf {} ({})", a.pair + 1, a.member + 1, teams[a.pair][a.member],
+                         b.pair + 1, b.member + 1, teams[b.pair][b.member]);
+            }
+        }
+    }
+}
+
+/// Classifies every pair of elves within a team.
+fn classify_pairs(team: &[Section]) -> impl Iterator<Item = OverlapKind> + '_ {
+    team.iter().enumerate().flat_map(|(index, a)| team[index + 1..].iter().map(move |b| classify(a, b)))
 }
 
-fn get_total_number_of_overlapping_ranges(input: String, full: bool) -> usize {
-    input.lines().into_iter().map(|it| it.trim())
-        .map(|it| get_pair_of_sections(it.to_string()))
-        .filter(|it| if full { fully_contained_in_ranges(&it[0], &it[1]) } else { check_overlap(&it[0], &it[1]) })
-        .count()
+/// Collects the kinds of the pairs found on every line.
+fn overlap_kinds_per_line(teams: &[Vec<Section>]) -> Vec<BTreeSet<OverlapKind>> {
+    teams.iter().map(|it| classify_pairs(it).collect()).collect()
 }
 
-fn get_pair_of_sections(input: String) -> Vec<Range<usize>> {
-    let mut sections = input.split(",");
-    sections.map(|it| {
-        let numbers = it.split("-")
-            .map(|b| b.parse::<usize>().unwrap()).collect::<Vec<usize>>();
-        numbers[0]..numbers[1]
-    }).collect()
+/// Counts the pairs of elves of every kind across all lines in a single pass.
+fn count_overlap_kinds(teams: &[Vec<Section>]) -> BTreeMap<OverlapKind, usize> {
+    let mut counts = BTreeMap::new();
+    for kind in teams.iter().flat_map(|it| classify_pairs(it)) {
+        *counts.entry(kind).or_insert(0) += 1;
+    }
+    counts
 }
 
-fn fully_contained_in_ranges(a: &Range<usize>, b: &Range<usize>) -> bool {
-    a.end <= b.end && a.start >= b.start ||
-        b.end <= a.end && b.start >= a.start
+/// Counts the lines with at least one pair of elves of a matching kind.
+fn count_where(kinds: &[BTreeSet<OverlapKind>], predicate: fn(&OverlapKind) -> bool) -> usize {
+    kinds.iter().filter(|it| it.iter().any(predicate)).count()
 }
 
-fn check_overlap(a: &Range<usize>, b: &Range<usize>) -> bool {
-    a.start <= b.start && a.end >= b.start || b.start <= a.start && b.end >= a.start
+fn get_pair_of_sections(input: String) -> Vec<Section> {
+    Section::parse_list(&input).unwrap_or_else(|error| panic!("Invalid assignments {input:?}: {error}"))
+}
+
+/// Parses the assignments of every line.
+fn get_teams(input: &str) -> Vec<Vec<Section>> {
+    input.lines().map(|it| get_pair_of_sections(it.trim().to_string())).collect()
 }
 
 #[cfg(test)]
@@ -48,19 +143,19 @@ mod tests {
         let result = get_pair_of_sections(input);
 
         // then: we get the correct pairs of sections
-        assert_eq!(result, vec![2..4, 6..8]);
+        assert_eq!(result, vec![Section::new(2, 4), Section::new(6, 8)]);
     }
 
     #[test]
     fn checks_if_range_is_fully_contained_by_the_other_range() {
         // given: two ranges
-        let ranges = vec![2..8, 3..7];
+        let ranges = [Section::new(2, 8), Section::new(3, 7)];
 
-        // when: fully_contained_in_ranges is called
-        let result = fully_contained_in_ranges(&ranges[0], &ranges[1]);
+        // when: the ranges are classified
+        let result = classify(&ranges[0], &ranges[1]);
 
         // then: we get the correct result
-        assert_eq!(result, true);
+        assert_eq!(result, OverlapKind::Contains);
     }
 
     #[test]
@@ -71,10 +166,10 @@ mod tests {
                 5-7,7-9
                 2-8,3-7
                 6-6,4-6
-                2-6,4-8"#.to_string();
+                2-6,4-8"#;
 
-        // when: get_total_number_of_fully_contained_ranges is called
-        let result = get_total_number_of_overlapping_ranges(input, true);
+        // when: the fully contained ranges are counted
+        let result = count_where(&overlap_kinds_per_line(&get_teams(input)), OverlapKind::is_containment);
 
         // then: we get the correct pairs of sections
         assert_eq!(result, 2);
@@ -83,25 +178,25 @@ mod tests {
     #[test]
     fn checks_if_ranges_overlap() {
         // given: two ranges
-        let ranges = vec![5..7, 7..9];
+        let ranges = [Section::new(5, 7), Section::new(7, 9)];
 
         // when: we check if they overlap
-        let result = check_overlap(&ranges[0], &ranges[1]);
+        let result = classify(&ranges[0], &ranges[1]);
 
         // then: we get the correct result
-        assert_eq!(result, true);
+        assert!(result.shares_sections());
     }
 
     #[test]
     fn checks_if_ranges_do_not_overlap() {
         // given: two ranges
-        let ranges = vec![2..4, 6..8];
+        let ranges = [Section::new(2, 4), Section::new(6, 8)];
 
         // when: we check if they overlap
-        let result = check_overlap(&ranges[0], &ranges[1]);
+        let result = classify(&ranges[0], &ranges[1]);
 
         // then: we get the correct result
-        assert_eq!(result, false);
+        assert!(!result.shares_sections());
     }
 
     #[test]
@@ -113,12 +208,64 @@ mod tests {
             2-8,3-7
             6-6,4-6
             2-6,4-8
-            9-9,4-6"#.to_string();
+            9-9,4-6"#;
 
-        // when: get_total_number_of_overlapping_ranges is called
-        let result = get_total_number_of_overlapping_ranges(input, false);
+        // when: the overlapping ranges are counted
+        let result = count_where(&overlap_kinds_per_line(&get_teams(input)), OverlapKind::shares_sections);
 
         // then: we get the correct pairs of sections
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn counts_teams_of_any_size() {
+        // given: teams of one, three and four elves
+        let input = r#"3-5
+            1-2,5-6,3-4
+            2-8,3-5,4-6,4-4"#;
+
+        // when: the overlapping teams are counted
+        let kinds = overlap_kinds_per_line(&get_teams(input));
+        let result = (count_where(&kinds, OverlapKind::is_containment), count_where(&kinds, OverlapKind::shares_sections));
+
+        // then: only the team of four is counted and the single elf is skipped
+        assert_eq!(result, (1, 1));
+    }
+
+    #[test]
+    fn collects_kinds_of_every_pair_in_teams_of_any_size() {
+        // given: teams of one, three and four elves
+        let input = r#"3-5
+            1-2,5-6,3-4
+            2-8,3-5,4-6,4-4"#;
+
+        // when: the kinds are collected
+        let result = overlap_kinds_per_line(&get_teams(input));
+
+        // then: every pair within a team is classified and the single elf has none
+        assert_eq!(result, vec![
+            BTreeSet::new(),
+            BTreeSet::from([OverlapKind::Adjacent, OverlapKind::Disjoint]),
+            BTreeSet::from([OverlapKind::Contains, OverlapKind::Overlaps]),
+        ]);
+    }
+
+    #[test]
+    fn counts_kinds_of_every_pair_in_teams_of_any_size() {
+        // given: teams of one, three and four elves
+        let input = r#"3-5
+            1-2,5-6,3-4
+            2-8,3-5,4-6,4-4"#;
+
+        // when: the kinds are counted
+        let result = count_overlap_kinds(&get_teams(input));
+
+        // then: every pair within a team is counted and the single elf is skipped
+        assert_eq!(result, BTreeMap::from([
+            (OverlapKind::Contains, 5),
+            (OverlapKind::Overlaps, 1),
+            (OverlapKind::Adjacent, 2),
+            (OverlapKind::Disjoint, 1),
+        ]));
+    }
 }
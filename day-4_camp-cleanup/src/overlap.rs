@@ -0,0 +1,75 @@
+use crate::section::Section;
+
+/// How two assignments relate to each other. Every pair has exactly one kind, checked in the
+/// order of the variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OverlapKind {
+    Equal,
+    /// One assignment contains the other one, which is shorter.
+    Contains,
+    /// The assignments share exactly their border section.
+    Touches,
+    /// The assignments share more than a border section without one containing the other.
+    Overlaps,
+    /// No shared section, but no section between them either.
+    Adjacent,
+    Disjoint,
+}
+
+impl OverlapKind {
+    /// Whether one elf only cleans sections their partner cleans anyway.
+    pub fn is_containment(&self) -> bool {
+        matches!(self, OverlapKind::Equal | OverlapKind::Contains)
+    }
+
+    pub fn shares_sections(&self) -> bool {
+        !matches!(self, OverlapKind::Adjacent | OverlapKind::Disjoint)
+    }
+}
+
+pub fn classify(a: &Section, b: &Section) -> OverlapKind {
+    if a == b {
+        OverlapKind::Equal
+    } else if a.contains(b) || b.contains(a) {
+        OverlapKind::Contains
+    } else if a.last() == b.first() || b.last() == a.first() {
+        OverlapKind::Touches
+    } else if a.overlaps(b) {
+        OverlapKind::Overlaps
+    } else if a.last() + 1 == b.first() || b.last() + 1 == a.first() {
+        OverlapKind::Adjacent
+    } else {
+        OverlapKind::Disjoint
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_every_kind_of_pair() {
+        // given: one pair of each kind
+        let pairs = [
+            (Section::new(3, 5), Section::new(3, 5)),
+            (Section::new(6, 6), Section::new(4, 6)),
+            (Section::new(5, 7), Section::new(7, 9)),
+            (Section::new(2, 6), Section::new(4, 8)),
+            (Section::new(2, 3), Section::new(4, 5)),
+            (Section::new(2, 4), Section::new(6, 8)),
+        ];
+
+        // when: the pairs are classified
+        let result = pairs.map(|(a, b)| (classify(&a, &b), classify(&b, &a)));
+
+        // then: each pair gets its kind regardless of the order
+        assert_eq!(result, [
+            (OverlapKind::Equal, OverlapKind::Equal),
+            (OverlapKind::Contains, OverlapKind::Contains),
+            (OverlapKind::Touches, OverlapKind::Touches),
+            (OverlapKind::Overlaps, OverlapKind::Overlaps),
+            (OverlapKind::Adjacent, OverlapKind::Adjacent),
+            (OverlapKind::Disjoint, OverlapKind::Disjoint),
+        ]);
+    }
+}
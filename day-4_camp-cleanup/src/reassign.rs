@@ -0,0 +1,135 @@
+use crate::interval_set::IntervalSet;
+use crate::section::Section;
+
+/// Teams up to this size are solved by trying every order; larger ones give the longest
+/// assignments precedence.
+const EXHAUSTIVE_TEAM_SIZE: usize = 8;
+
+/// A change to the assignment of one elf. Members are numbered from 0 in the order of the input.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Fix {
+    Trim { member: usize, to: Section },
+    Split { member: usize, into: Vec<Section> },
+    Drop { member: usize },
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct Reassignment {
+    /// The new assignments of every member; dropped members have none, split members several.
+    pub assignments: Vec<Vec<Section>>,
+    pub fixes: Vec<Fix>,
+}
+
+impl Reassignment {
+    /// Formats the assignments like a line of the puzzle input.
+    pub fn to_line(&self) -> String {
+        self.assignments.iter().flatten().map(|it| it.to_string()).collect::<Vec<String>>().join(",")
+    }
+}
+
+/// Removes every overlap within the team while changing as few assignments as possible. Each
+/// member in turn keeps the sections nobody before them has claimed, so every section is still
+/// cleaned by exactly one elf; the order with the fewest fixes wins.
+pub fn reassign(sections: &[Section]) -> Reassignment {
+    let mut best: Option<Reassignment> = None;
+    for order in orders(sections) {
+        let candidate = apply_order(sections, &order);
+        if best.as_ref().is_none_or(|it| candidate.fixes.len() < it.fixes.len()) {
+            best = Some(candidate);
+        }
+    }
+    best.unwrap_or_default()
+}
+
+fn apply_order(sections: &[Section], order: &[usize]) -> Reassignment {
+    let mut assignments = vec![vec![]; sections.len()];
+    let mut claimed = IntervalSet::default();
+    for member in order {
+        let own = sections[*member].sections();
+        assignments[*member] = Section::from_interval_set(&own.difference(&claimed));
+        claimed = claimed.union(&own);
+    }
+    let fixes = assignments.iter().enumerate()
+        .filter_map(|(member, it)| match it.as_slice() {
+            [] => Some(Fix::Drop { member }),
+            [section] if *section == sections[member] => None,
+            [section] => Some(Fix::Trim { member, to: section.clone() }),
+            _ => Some(Fix::Split { member, into: it.clone() }),
+        })
+        .collect();
+    Reassignment { assignments, fixes }
+}
+
+fn orders(sections: &[Section]) -> Vec<Vec<usize>> {
+    if sections.len() > EXHAUSTIVE_TEAM_SIZE {
+        let mut order = (0..sections.len()).collect::<Vec<usize>>();
+        order.sort_by_key(|it| std::cmp::Reverse(sections[*it].last() - sections[*it].first()));
+        return vec![order];
+    }
+    let mut orders = vec![];
+    permute(&mut (0..sections.len()).collect::<Vec<usize>>(), 0, &mut orders);
+    orders
+}
+
+fn permute(order: &mut [usize], start: usize, orders: &mut Vec<Vec<usize>>) {
+    if start == order.len() {
+        orders.push(order.to_vec());
+        return;
+    }
+    for index in start..order.len() {
+        order.swap(start, index);
+        permute(order, start + 1, orders);
+        order.swap(start, index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_teams;
+
+    fn reassign_line(line: &str) -> Reassignment {
+        reassign(&get_teams(line)[0])
+    }
+
+    #[test]
+    fn trims_and_drops_overlapping_pairs() {
+        // given: a partial overlap, a contained assignment and a disjoint pair
+        let lines = ["5-7,7-9", "2-8,3-7", "2-4,6-8"];
+
+        // when: the pairs are reassigned
+        let result = lines.map(reassign_line);
+
+        // then: the overlaps are removed with a single fix each
+        assert_eq!(result[0].fixes, vec![Fix::Trim { member: 1, to: Section::new(8, 9) }]);
+        assert_eq!(result[1].fixes, vec![Fix::Drop { member: 1 }]);
+        assert!(result[2].fixes.is_empty());
+        assert_eq!(result.map(|it| it.to_line()), ["5-7,8-9", "2-8", "2-4,6-8"]);
+    }
+
+    #[test]
+    fn prefers_the_order_with_the_fewest_fixes() {
+        // given: an assignment overlapping two others
+        let line = "1-3,7-9,2-8";
+
+        // when: the team is reassigned
+        let result = reassign_line(line);
+
+        // then: only the middle assignment is trimmed
+        assert_eq!(result.fixes, vec![Fix::Trim { member: 2, to: Section::new(4, 6) }]);
+        assert_eq!(result.to_line(), "1-3,7-9,4-6");
+    }
+
+    #[test]
+    fn splits_an_assignment_around_others() {
+        // given: a long assignment containing two short ones
+        let line = "1-9,2-3,6-7";
+
+        // when: the team is reassigned
+        let result = reassign_line(line);
+
+        // then: the long assignment is split instead of dropping both short ones
+        assert_eq!(result.fixes, vec![Fix::Split { member: 0, into: vec![Section::new(1, 1), Section::new(4, 5), Section::new(8, 9)] }]);
+        assert_eq!(result.to_line(), "1-1,4-5,8-9,2-3,6-7");
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
+use crate::interval_set::IntervalSet;
+
+/// Wide enough to hold every `i64` and every `u64` section ID.
+pub type SectionId = i128;
+
+/// An assignment of section IDs, where both the first and the last section are included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    range: RangeInclusive<SectionId>,
+}
+
+impl Section {
+    pub fn new(first: SectionId, last: SectionId) -> Self {
+        Section { range: first..=last }
+    }
+
+    /// Parses a comma separated list of assignments. An assignment is either written as
+    /// `first-last` with unsigned IDs like in the puzzle input, or as `first..=last` or
+    /// `[first,last]`, which also allow negative IDs. A bare `first..last` is rejected, as it
+    /// would read as excluding the last section. IDs have to fit into an `i64` or a `u64`.
+    pub fn parse_list(input: &str) -> Result<Vec<Section>, String> {
+        let mut sections = vec![];
+        let mut depth = 0;
+        let mut start = 0;
+        for (index, char) in input.char_indices() {
+            match char {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                ',' if depth == 0 => {
+                    sections.push(Section::parse(&input[start..index])?);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+        sections.push(Section::parse(&input[start..])?);
+        Ok(sections)
+    }
+
+    pub fn parse(input: &str) -> Result<Section, String> {
+        let input = input.trim();
+        let (first, last) = if let Some(bounds) = input.strip_prefix('[').and_then(|it| it.strip_suffix(']')) {
+            let (first, last) = bounds.split_once(',').ok_or(format!("Expected [first,last] but got {input:?}"))?;
+            (parse_id(first)?, parse_id(last)?)
+        } else if let Some((first, last)) = input.split_once("..") {
+            let last = last.strip_prefix('=').ok_or(format!("Expected first..=last but got {input:?}"))?;
+            (parse_id(first)?, parse_id(last)?)
+        } else {
+            let (first, last) = input.split_once('-').ok_or(format!("Expected first-last but got {input:?}"))?;
+            (parse_unsigned_id(first)?, parse_unsigned_id(last)?)
+        };
+        if first > last {
+            return Err(format!("The first section of {input:?} comes after the last one"));
+        }
+        Ok(Section::new(first, last))
+    }
+
+    pub fn first(&self) -> SectionId {
+        *self.range.start()
+    }
+
+    pub fn last(&self) -> SectionId {
+        *self.range.end()
+    }
+
+    /// The assigned sections as a set of half-open ranges.
+    pub fn sections(&self) -> IntervalSet<SectionId> {
+        IntervalSet::from_ranges(std::iter::once(self.first()..self.last() + 1))
+    }
+
+    /// Splits a set of sections into one assignment per contiguous range.
+    pub fn from_interval_set(set: &IntervalSet<SectionId>) -> Vec<Section> {
+        set.ranges().iter().map(|it| Section::new(it.start, it.end - 1)).collect()
+    }
+
+    /// Whether every section of `other` is also part of this assignment.
+    pub fn contains(&self, other: &Section) -> bool {
+        self.sections().contains_set(&other.sections())
+    }
+
+    /// Whether the assignments together cover fewer sections than they do on their own.
+    pub fn overlaps(&self, other: &Section) -> bool {
+        let (a, b) = (self.sections(), other.sections());
+        a.union(&b).coverage() < a.coverage() + b.coverage()
+    }
+}
+
+fn parse_id(input: &str) -> Result<SectionId, String> {
+    let id = input.trim().parse::<SectionId>().map_err(|_| format!("{input:?} is not a section ID"))?;
+    if id < i64::MIN as SectionId || id > u64::MAX as SectionId {
+        return Err(format!("{input:?} does not fit into 64 bits"));
+    }
+    Ok(id)
+}
+
+fn parse_unsigned_id(input: &str) -> Result<SectionId, String> {
+    input.trim().parse::<u64>().map(SectionId::from).map_err(|_| format!("{input:?} is not an unsigned section ID"))
+}
+
+/// Uses the format of the puzzle input, unless a negative ID requires the range syntax.
+impl Display for Section {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.first() < 0 {
+            write!(f, "{}..={}", self.first(), self.last())
+        } else {
+            write!(f, "{}-{}", self.first(), self.last())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handles_single_section_assignments() {
+        // given: a single section and an assignment around it
+        let single = Section::new(6, 6);
+        let around = Section::new(4, 6);
+
+        // when / then: the single section is contained and overlaps
+        assert!(around.contains(&single));
+        assert!(!single.contains(&around));
+        assert!(single.contains(&single));
+        assert!(single.overlaps(&around));
+        assert!(!single.overlaps(&Section::new(7, 7)));
+    }
+
+    #[test]
+    fn distinguishes_touching_from_adjacent_assignments() {
+        // given: assignments sharing their border section and assignments next to each other
+        let touching = (Section::new(5, 7), Section::new(7, 9));
+        let adjacent = (Section::new(2, 3), Section::new(4, 5));
+
+        // when / then: only the ones sharing a section overlap
+        assert!(touching.0.overlaps(&touching.1));
+        assert!(touching.1.overlaps(&touching.0));
+        assert!(!adjacent.0.overlaps(&adjacent.1));
+        assert!(!adjacent.1.overlaps(&adjacent.0));
+        assert!(!touching.0.contains(&touching.1));
+    }
+
+    #[test]
+    fn parses_every_range_syntax() {
+        // given: assignments in the original, the range and the bracket syntax
+        let input = "2-4, -5..=-2,[-3,7],0..=9";
+
+        // when: they are parsed
+        let result = Section::parse_list(input);
+
+        // then: every assignment is read with its sign
+        assert_eq!(result, Ok(vec![Section::new(2, 4), Section::new(-5, -2), Section::new(-3, 7), Section::new(0, 9)]));
+    }
+
+    #[test]
+    fn parses_64_bit_ids() {
+        // given: the extremes of i64 and u64
+        let input = format!("{}..=-1,1-{}", i64::MIN, u64::MAX);
+
+        // when: they are parsed
+        let result = Section::parse_list(&input).unwrap();
+
+        // then: both are kept and the original format is written back
+        assert_eq!(result, vec![Section::new(i64::MIN as SectionId, -1), Section::new(1, u64::MAX as SectionId)]);
+        assert_eq!(result.iter().map(|it| it.to_string()).collect::<Vec<String>>().join(","), input);
+    }
+
+    #[test]
+    fn rejects_invalid_assignments() {
+        // given: ambiguous, reversed and oversized assignments
+        let inputs = ["-5-2", "0..9", "7-3", "[1;2]", &format!("0..={}", u64::MAX as SectionId + 1)];
+
+        // when: they are parsed
+        let result = inputs.map(Section::parse_list);
+
+        // then: all of them are rejected
+        assert!(result.iter().all(|it| it.is_err()), "{result:?}");
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::BTreeSet;
+
+use crate::camp::{list_elves, Elf};
+use crate::section::{Section, SectionId};
+
+#[derive(Debug, PartialEq, Default)]
+pub struct OverlapReport {
+    /// Every pair of elves sharing at least one section.
+    pub overlapping: Vec<(Elf, Elf)>,
+    /// Every pair where the first elf's assignment contains the second one's. Equal assignments
+    /// are only listed once.
+    pub containing: Vec<(Elf, Elf)>,
+}
+
+/// Compares the assignments of all elves, not only the ones within a pair. The elves are swept
+/// by their first section while the active ones are kept ordered by their last section, which
+/// takes O(n log n) time plus the number of reported pairs.
+pub fn find_all_overlaps(pairs: &[Vec<Section>]) -> OverlapReport {
+    let mut elves = list_elves(pairs);
+    // longer assignments come first, so they are active before the ones they contain
+    elves.sort_by_key(|(_, it)| (it.first(), std::cmp::Reverse(it.last())));
+
+    let mut report = OverlapReport::default();
+    let mut active: BTreeSet<(SectionId, usize)> = BTreeSet::new();
+    for (index, (elf, section)) in elves.iter().enumerate() {
+        while let Some((last, _)) = active.first() {
+            if *last >= section.first() {
+                break;
+            }
+            active.pop_first();
+        }
+        // every active elf starts no later and ends no earlier than the first section
+        for (last, other) in &active {
+            report.overlapping.push((elves[*other].0, *elf));
+            if *last >= section.last() {
+                report.containing.push((elves[*other].0, *elf));
+            }
+        }
+        active.insert((section.last(), index));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_teams;
+
+    #[test]
+    fn finds_pairs_across_lines() {
+        // given: assignments that overlap with the ones on other lines
+        let pairs = get_teams(r#"1-5,2-3
+            3-3,6-7"#);
+
+        // when: all overlaps are searched
+        let result = find_all_overlaps(&pairs);
+
+        // then: the pairs within and across lines are reported
+        let (a, b, c) = (Elf { pair: 0, member: 0 }, Elf { pair: 0, member: 1 }, Elf { pair: 1, member: 0 });
+        assert_eq!(result.overlapping, vec![(a, b), (b, c), (a, c)]);
+        assert_eq!(result.containing, vec![(a, b), (b, c), (a, c)]);
+    }
+
+    #[test]
+    fn agrees_with_comparing_every_pair() {
+        // given: the example assignments
+        let pairs = get_teams(r#"2-4,6-8
+            2-3,4-5
+            5-7,7-9
+            2-8,3-7
+            6-6,4-6
+            2-6,4-8
+            4-6,9-9"#);
+
+        // when: all overlaps are searched
+        let result = find_all_overlaps(&pairs);
+
+        // then: the counts match a comparison of every pair
+        let elves = pairs.concat();
+        let mut overlapping = 0;
+        let mut containing = 0;
+        for (index, a) in elves.iter().enumerate() {
+            for b in &elves[index + 1..] {
+                overlapping += a.overlaps(b) as usize;
+                containing += (a.contains(b) || b.contains(a)) as usize;
+            }
+        }
+        assert_eq!((result.overlapping.len(), result.containing.len()), (overlapping, containing));
+    }
+}
@@ -0,0 +1,60 @@
+use crate::section::Section;
+
+/// Whether any two assignments of the team share a section.
+pub fn any_overlap(sections: &[Section]) -> bool {
+    sections.iter().enumerate().any(|(index, a)| sections[index + 1..].iter().any(|b| a.overlaps(b)))
+}
+
+/// Position of an assignment that contains all the other ones of the team.
+pub fn containing_all(sections: &[Section]) -> Option<usize> {
+    sections.iter().position(|it| sections.iter().all(|other| it.contains(other)))
+}
+
+/// Sections assigned to every elf of the team.
+pub fn common_intersection(sections: &[Section]) -> Option<Section> {
+    let first = sections.iter().map(|it| it.first()).max()?;
+    let last = sections.iter().map(|it| it.last()).min()?;
+    (first <= last).then(|| Section::new(first, last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_teams;
+
+    #[test]
+    fn answers_queries_for_larger_teams() {
+        // given: a team of four elves and a team of three
+        let teams = get_teams("2-8,3-5,4-6,4-4\n1-2,5-6,3-4");
+        let (four, three) = (&teams[0], &teams[1]);
+
+        // when / then: the queries consider every assignment
+        assert!(any_overlap(four));
+        assert_eq!(containing_all(four), Some(0));
+        assert_eq!(common_intersection(four), Some(Section::new(4, 4)));
+        assert!(!any_overlap(three));
+        assert_eq!(containing_all(three), None);
+        assert_eq!(common_intersection(three), None);
+    }
+
+    #[test]
+    fn finds_overlaps_hidden_behind_a_long_assignment() {
+        // given: a long assignment and one inside it that follows a short one
+        let sections = [Section::new(1, 9), Section::new(2, 2), Section::new(8, 8)];
+
+        // when / then: the overlap with the long assignment is found
+        assert!(any_overlap(&sections));
+        assert!(!any_overlap(&sections[1..]));
+    }
+
+    #[test]
+    fn handles_teams_of_one() {
+        // given: a single assignment
+        let sections = [Section::new(3, 5)];
+
+        // when / then: it neither overlaps nor is missing from the intersection
+        assert!(!any_overlap(&sections));
+        assert_eq!(containing_all(&sections), Some(0));
+        assert_eq!(common_intersection(&sections), Some(Section::new(3, 5)));
+    }
+}
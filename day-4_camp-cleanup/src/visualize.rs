@@ -0,0 +1,86 @@
+use crate::interval_set::IntervalSet;
+use crate::section::{Section, SectionId};
+
+/// Draws every team like the puzzle description, one row per assignment followed by a row
+/// marking the sections shared within the team. Section 1 is drawn in the first column. Camps
+/// wider than `max_width` are scaled down, so that every column stands for several sections;
+/// a column is filled as soon as one of its sections is.
+pub fn visualize(teams: &[Vec<Section>], max_width: usize) -> String {
+    let sections = teams.iter().flatten();
+    let origin = sections.clone().map(|it| it.first()).min().unwrap_or(1).min(1);
+    let end = sections.map(|it| it.last() + 1).max().unwrap_or(origin);
+    let span = (end - origin) as u128;
+    let scale = span.div_ceil(max_width.max(1) as u128).max(1);
+    let columns = span.div_ceil(scale) as usize;
+    let column = |index: usize| {
+        let start = origin + index as SectionId * scale as SectionId;
+        let scale = scale as SectionId;
+        IntervalSet::from_ranges(std::iter::once(start..start + scale))
+    };
+
+    let mut drawing = String::new();
+    if scale > 1 {
+        drawing += &format!("Each column stands for {scale} sections\n\n");
+    }
+    for (index, team) in teams.iter().enumerate() {
+        if index > 0 {
+            drawing += "\n";
+        }
+        let mut shared = IntervalSet::default();
+        for (member, section) in team.iter().enumerate() {
+            let row = (0..columns)
+                .map(|index| match column(index).overlaps(&section.sections()) {
+                    false => '.',
+                    true if scale > 1 => '=',
+                    true => char::from_digit((origin + index as SectionId).rem_euclid(10) as u32, 10).unwrap(),
+                })
+                .collect::<String>();
+            drawing += &format!("{row}  {section}\n");
+            for other in &team[member + 1..] {
+                shared = shared.union(&section.sections().intersection(&other.sections()));
+            }
+        }
+        if !shared.is_empty() {
+            let row = (0..columns).map(|index| if column(index).overlaps(&shared) { '#' } else { ' ' }).collect::<String>();
+            drawing += &format!("{row}  overlap\n");
+        }
+    }
+    drawing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn draws_teams_like_the_puzzle_description() {
+        // given: a disjoint and an overlapping pair from the example
+        let teams = [
+            vec![Section::new(2, 4), Section::new(6, 8)],
+            vec![Section::new(5, 7), Section::new(7, 9)],
+        ];
+
+        // when: they are drawn
+        let result = visualize(&teams, 80);
+
+        // then: the shared section is marked below the overlapping pair
+        assert_eq!(result, ".234.....  2-4\n.....678.  6-8\n\n....567..  5-7\n......789  7-9\n      #    overlap\n");
+    }
+
+    #[test]
+    fn scales_down_wide_camps() {
+        // given: assignments spanning thousands of sections
+        let teams = [vec![Section::new(1, 2000), Section::new(1500, 4000)]];
+
+        // when: they are drawn in at most 40 columns
+        let result = visualize(&teams, 40);
+
+        // then: every column stands for 100 sections
+        assert_eq!(result, concat!(
+            "Each column stands for 100 sections\n\n",
+            "====================....................  1-2000\n",
+            "..............==========================  1500-4000\n",
+            "              ######                      overlap\n",
+        ));
+    }
+}
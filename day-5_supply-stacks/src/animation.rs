@@ -0,0 +1,110 @@
+use std::iter::Enumerate;
+use std::ops::RangeInclusive;
+use std::slice::Iter;
+
+use crate::crane::Crane;
+use crate::warehouse::Warehouse;
+use crate::Instruction;
+
+#[derive(Debug, PartialEq)]
+pub struct Frame {
+    /// Number of instructions executed so far, so the starting drawing is step 0.
+    pub step: usize,
+    /// The instruction that led to this frame.
+    pub instruction: Option<String>,
+    pub drawing: String,
+}
+
+/// Executes the instructions one at a time while it is iterated, so every frame can be shown
+/// as soon as it is drawn. Stops after the first failing instruction.
+pub struct Frames<'a> {
+    warehouse: Warehouse,
+    instructions: Enumerate<Iter<'a, Instruction>>,
+    crane: &'a mut dyn Crane,
+    steps: RangeInclusive<usize>,
+    started: bool,
+    failed: bool,
+}
+
+/// Draws the warehouse after each instruction. Only the frames of the steps within `steps` are
+/// returned, but the instructions before them still have to be executed.
+pub fn render_frames<'a>(warehouse: Warehouse, instructions: &'a [Instruction], crane: &'a mut dyn Crane, steps: RangeInclusive<usize>) -> Frames<'a> {
+    Frames { warehouse, instructions: instructions.iter().enumerate(), crane, steps, started: false, failed: false }
+}
+
+impl Iterator for Frames<'_> {
+    type Item = Result<Frame, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if self.steps.contains(&0) {
+                return Some(Ok(Frame { step: 0, instruction: None, drawing: self.warehouse.to_string() }));
+            }
+        }
+        if self.failed {
+            return None;
+        }
+        loop {
+            let (index, instruction) = self.instructions.next().filter(|(index, _)| index < self.steps.end())?;
+            if let Err(error) = self.crane.execute(&mut self.warehouse, instruction) {
+                self.failed = true;
+                return Some(Err(format!("Instruction {}: {error}", index + 1)));
+            }
+            if self.steps.contains(&(index + 1)) {
+                return Some(Ok(Frame { step: index + 1, instruction: Some(instruction.to_string()), drawing: self.warehouse.to_string() }));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crane::CrateMover9000;
+    use crate::parse_instructions;
+
+    #[test]
+    fn renders_frames_within_the_range() {
+        // given: the example warehouse and instructions
+        let warehouse = Warehouse::parse("    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3").unwrap();
+        let instructions = parse_instructions("move 1 from 2 to 1\nmove 3 from 1 to 3\nmove 2 from 2 to 1".to_string());
+
+        // when: the first two steps are rendered
+        let result = render_frames(warehouse, &instructions, &mut CrateMover9000, 1..=2).collect::<Result<Vec<Frame>, String>>().unwrap();
+
+        // then: we get the drawings of the puzzle description
+        assert_eq!(result, vec![
+            Frame { step: 1, instruction: Some("move 1 from 2 to 1".to_string()), drawing: "[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3".to_string() },
+            Frame { step: 2, instruction: Some("move 3 from 1 to 3".to_string()), drawing: "        [Z]\n        [N]\n    [C] [D]\n    [M] [P]\n 1   2   3".to_string() },
+        ]);
+    }
+
+    #[test]
+    fn renders_the_starting_drawing_as_step_zero() {
+        // given: a warehouse without instructions
+        let warehouse = Warehouse::parse("[A]\n 1").unwrap();
+
+        // when: every step is rendered
+        let result = render_frames(warehouse, &[], &mut CrateMover9000, 0..=usize::MAX).collect::<Result<Vec<Frame>, String>>().unwrap();
+
+        // then: only the starting drawing is returned
+        assert_eq!(result, vec![Frame { step: 0, instruction: None, drawing: "[A]\n 1".to_string() }]);
+    }
+
+    #[test]
+    fn renders_the_frames_before_a_failing_instruction() {
+        // given: a warehouse and an instruction that cannot be executed
+        let warehouse = Warehouse::parse("[A]\n 1   2").unwrap();
+        let instructions = parse_instructions("move 1 from 1 to 2\nmove 2 from 2 to 1\nmove 1 from 2 to 1".to_string());
+
+        // when: the frames are rendered
+        let result = render_frames(warehouse, &instructions, &mut CrateMover9000, 1..=3).collect::<Vec<Result<Frame, String>>>();
+
+        // then: the frame of the first step is followed by the error and nothing else
+        assert_eq!(result, vec![
+            Ok(Frame { step: 1, instruction: Some("move 1 from 1 to 2".to_string()), drawing: "    [A]\n 1   2".to_string() }),
+            Err("Instruction 2: Stack 2 does not hold 2 crates".to_string()),
+        ]);
+    }
+}
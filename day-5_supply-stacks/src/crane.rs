@@ -0,0 +1,194 @@
+use crate::warehouse::Warehouse;
+use crate::Instruction;
+
+pub trait Crane {
+    fn name(&self) -> String;
+
+    fn execute(&mut self, warehouse: &mut Warehouse, instruction: &Instruction) -> Result<(), String>;
+}
+
+/// Moves one crate at a time, so the moved crates end up in reverse order.
+pub struct CrateMover9000;
+
+/// Moves all crates at once and keeps their order.
+pub struct CrateMover9001;
+
+/// Moves at most `capacity` crates at once, keeping the order within every lift.
+pub struct CappedCrane {
+    pub capacity: usize,
+}
+
+/// Keeps the order on the first move and reverses it on the second, and so on.
+#[derive(Default)]
+pub struct AlternatingCrane {
+    moves: usize,
+}
+
+/// Picks the crates one at a time from the top, alternating between both arms. Then the first
+/// arm puts its crates down in the order they were picked, followed by the second arm.
+pub struct TwoArmCrane;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "Crate Mover 9000".to_string()
+    }
+
+    fn execute(&mut self, warehouse: &mut Warehouse, instruction: &Instruction) -> Result<(), String> {
+        let mut crates = take(warehouse, instruction, instruction.iterations)?;
+        crates.reverse();
+        put(warehouse, instruction, crates)
+    }
+}
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "Crate Mover 9001".to_string()
+    }
+
+    fn execute(&mut self, warehouse: &mut Warehouse, instruction: &Instruction) -> Result<(), String> {
+        let crates = take(warehouse, instruction, instruction.iterations)?;
+        put(warehouse, instruction, crates)
+    }
+}
+
+impl Crane for CappedCrane {
+    fn name(&self) -> String {
+        format!("Capped crane ({} crates)", self.capacity)
+    }
+
+    fn execute(&mut self, warehouse: &mut Warehouse, instruction: &Instruction) -> Result<(), String> {
+        let mut remaining = instruction.iterations;
+        while remaining > 0 {
+            let count = remaining.min(self.capacity);
+            let crates = take(warehouse, instruction, count)?;
+            put(warehouse, instruction, crates)?;
+            remaining -= count;
+        }
+        Ok(())
+    }
+}
+
+impl Crane for AlternatingCrane {
+    fn name(&self) -> String {
+        "Alternating crane".to_string()
+    }
+
+    fn execute(&mut self, warehouse: &mut Warehouse, instruction: &Instruction) -> Result<(), String> {
+        let mut crates = take(warehouse, instruction, instruction.iterations)?;
+        if !self.moves.is_multiple_of(2) {
+            crates.reverse();
+        }
+        self.moves += 1;
+        put(warehouse, instruction, crates)
+    }
+}
+
+impl Crane for TwoArmCrane {
+    fn name(&self) -> String {
+        "Two-arm crane".to_string()
+    }
+
+    fn execute(&mut self, warehouse: &mut Warehouse, instruction: &Instruction) -> Result<(), String> {
+        let mut picked = take(warehouse, instruction, instruction.iterations)?;
+        picked.reverse();
+        let first = picked.iter().step_by(2);
+        let second = picked.iter().skip(1).step_by(2);
+        put(warehouse, instruction, first.chain(second).cloned().collect())
+    }
+}
+
+/// Parses a crane model as given on the command line: `9000`, `9001`, `capped:<capacity>`,
+/// `alternating` or `two-arm`.
+pub fn parse_crane(input: &str) -> Option<Box<dyn Crane>> {
+    match input {
+        "9000" => Some(Box::new(CrateMover9000)),
+        "9001" => Some(Box::new(CrateMover9001)),
+        "alternating" => Some(Box::new(AlternatingCrane::default())),
+        "two-arm" => Some(Box::new(TwoArmCrane)),
+        _ => {
+            let capacity = input.strip_prefix("capped:")?.parse::<usize>().ok().filter(|it| *it > 0)?;
+            Some(Box::new(CappedCrane { capacity }))
+        }
+    }
+}
+
+fn take(warehouse: &mut Warehouse, instruction: &Instruction, count: usize) -> Result<Vec<String>, String> {
+    instruction.from.checked_sub(1).and_then(|from| warehouse.take(from, count))
+        .ok_or(format!("Stack {} does not hold {count} crates", instruction.from))
+}
+
+fn put(warehouse: &mut Warehouse, instruction: &Instruction, crates: Vec<String>) -> Result<(), String> {
+    instruction.to.checked_sub(1).and_then(|to| warehouse.put(to, crates))
+        .ok_or(format!("There is no stack {}", instruction.to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{execute_instructions, format_top_crates, parse_instructions};
+
+    const EXAMPLE: &str = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3";
+
+    const INSTRUCTIONS: &str = r#"move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2"#;
+
+    fn top_crates(crane: &mut dyn Crane) -> String {
+        let warehouse = Warehouse::parse(EXAMPLE).unwrap();
+        format_top_crates(&execute_instructions(warehouse, parse_instructions(INSTRUCTIONS.to_string()), crane).unwrap())
+    }
+
+    #[test]
+    fn runs_every_crane_model() {
+        // given: the example warehouse and instructions
+
+        // when: every crane rearranges the crates
+        let result = [
+            top_crates(&mut CrateMover9000),
+            top_crates(&mut CrateMover9001),
+            top_crates(&mut CappedCrane { capacity: 2 }),
+            top_crates(&mut AlternatingCrane::default()),
+            top_crates(&mut TwoArmCrane),
+        ];
+
+        // then: each model ends with its own top crates
+        assert_eq!(result, ["CMZ", "MCD", "MCZ", "MCZ", "CMN"]);
+    }
+
+    #[test]
+    fn keeps_the_order_within_each_lift() {
+        // given: a stack of five crates
+        let mut warehouse = Warehouse::parse("[E]\n[D]\n[C]\n[B]\n[A]\n 1   2").unwrap();
+        let instruction = Instruction { iterations: 5, from: 1, to: 2 };
+
+        // when: a crane lifting two crates at once moves all of them
+        CappedCrane { capacity: 2 }.execute(&mut warehouse, &instruction).unwrap();
+
+        // then: the lifts are stacked in reverse order
+        assert_eq!(warehouse.to_string(), "    [A]\n    [C]\n    [B]\n    [E]\n    [D]\n 1   2");
+    }
+
+    #[test]
+    fn parses_crane_models_and_reports_missing_crates() {
+        // given: crane names from the command line
+        let names = ["9000", "9001", "capped:3", "alternating", "two-arm", "capped:0", "9002"];
+
+        // when: they are parsed
+        let result = names.map(|it| parse_crane(it).map(|it| it.name()));
+
+        // then: only valid models are accepted
+        assert_eq!(result, [
+            Some("Crate Mover 9000".to_string()),
+            Some("Crate Mover 9001".to_string()),
+            Some("Capped crane (3 crates)".to_string()),
+            Some("Alternating crane".to_string()),
+            Some("Two-arm crane".to_string()),
+            None,
+            None,
+        ]);
+        let mut warehouse = Warehouse::parse(EXAMPLE).unwrap();
+        let result = CrateMover9000.execute(&mut warehouse, &Instruction { iterations: 2, from: 3, to: 1 });
+        assert_eq!(result, Err("Stack 3 does not hold 2 crates".to_string()));
+    }
+}
@@ -1,75 +1,119 @@
-use std::collections::LinkedList;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::time::Duration;
+
+use crate::animation::render_frames;
+use crate::crane::{parse_crane, Crane, CrateMover9000, CrateMover9001};
+use crate::warehouse::Warehouse;
+
+mod animation;
+mod crane;
+mod warehouse;
 
 fn main() {
+    let args = std::env::args().collect::<Vec<String>>();
+    let cranes: Vec<Box<dyn Crane>> = match args.iter().position(|it| it == "--crane") {
+        Some(index) => vec![args.get(index + 1).and_then(|it| parse_crane(it))
+            .expect("--crane must be one of 9000, 9001, capped:<capacity>, alternating, two-arm")],
+        None => vec![Box::new(CrateMover9000), Box::new(CrateMover9001)],
+    };
+
     let input = std::fs::read_to_string("input.txt").unwrap();
-    let stacks = restack_crates_and_return_top_crate(input.clone(), false);
-    println!("[Crate Mover 9000] The top crates are: {stacks:?}");
+    if args.iter().any(|it| it == "--animate") {
+        animate(input, cranes.into_iter().next().unwrap(), &args);
+        return;
+    }
 
-    let stacks = restack_crates_and_return_top_crate(input, true);
-    println!("[Crate Mover 9001] The top crates are: {stacks:?}");
+    for mut crane in cranes {
+        match restack_crates_and_return_top_crate(input.clone(), crane.as_mut()) {
+            Ok(stacks) => println!("[{}] The top crates are: {stacks}", crane.name()),
+            Err(error) => eprintln!("[{}] Cannot restack the crates: {error}", crane.name()),
+        }
+    }
 }
 
-fn restack_crates_and_return_top_crate(input: String, runs_on_crate_mover_9001:bool) -> Vec<char> {
+/// Renders the warehouse after every instruction. `--from` and `--to` limit the steps, `--step`
+/// waits for enter after every frame, `--delay <ms>` animates the frames in place and
+/// `--frames <directory>` writes every frame to its own file.
+fn animate(input: String, mut crane: Box<dyn Crane>, args: &[String]) {
+    let number_after = |flag: &str| args.iter().position(|it| it == flag)
+        .map(|index| args.get(index + 1).and_then(|it| it.parse::<usize>().ok()).unwrap_or_else(|| panic!("{flag} needs a number")));
+    let steps = number_after("--from").unwrap_or(0)..=number_after("--to").unwrap_or(usize::MAX);
+    let delay = number_after("--delay").map(|it| Duration::from_millis(it as u64));
+    let directory = args.iter().position(|it| it == "--frames")
+        .map(|index| args.get(index + 1).expect("--frames needs a directory"));
+    let waits_for_enter = args.iter().any(|it| it == "--step");
+
+    let name = crane.name();
     let (stacks, instructions) = get_stacks_and_instructions(input);
-    let mut stacks = parse_stacks(stacks);
+    let warehouse = match Warehouse::parse(&stacks) {
+        Ok(warehouse) => warehouse,
+        Err(error) => {
+            eprintln!("[{name}] Cannot restack the crates: {error}");
+            return;
+        }
+    };
+    if let Some(directory) = directory {
+        std::fs::create_dir_all(directory).unwrap();
+    }
     let instructions = parse_instructions(instructions);
-    let sorted_stacks = execute_instructions(stacks, instructions, runs_on_crate_mover_9001);
-
-    sorted_stacks.iter().map(|it| it.back().unwrap().clone()).collect()
+    let mut stdin = std::io::stdin().lock();
+    for frame in render_frames(warehouse, &instructions, crane.as_mut(), steps) {
+        let frame = match frame {
+            Ok(frame) => frame,
+            Err(error) => {
+                eprintln!("[{name}] Cannot restack the crates: {error}");
+                return;
+            }
+        };
+        if delay.is_some() {
+            // clears the terminal, so the frames replace each other
+            print!("\x1b[2J\x1b[H");
+        }
+        println!("Step {}: {}", frame.step, frame.instruction.as_deref().unwrap_or("start"));
+        println!("{}\n", frame.drawing);
+        if let Some(directory) = directory {
+            std::fs::write(format!("{directory}/frame-{:04}.txt", frame.step), format!("{}\n", frame.drawing)).unwrap();
+        }
+        if waits_for_enter {
+            stdin.read_line(&mut String::new()).unwrap();
+        }
+        if let Some(delay) = delay {
+            std::thread::sleep(delay);
+        }
+    }
 }
 
-fn get_stacks_and_instructions(input: String) -> (String, String) {
-    input.split("\n\n").partition(|it| !it.contains("move"))
+fn restack_crates_and_return_top_crate(input: String, crane: &mut dyn Crane) -> Result<String, String> {
+    let (stacks, instructions) = get_stacks_and_instructions(input);
+    let warehouse = Warehouse::parse(&stacks)?;
+    let instructions = parse_instructions(instructions);
+    let sorted_warehouse = execute_instructions(warehouse, instructions, crane)?;
+
+    Ok(format_top_crates(&sorted_warehouse))
 }
 
-fn parse_stacks(input: String) -> Vec<LinkedList<char>> {
-    let lines = input.lines();
-    let mut stacks = vec![];
-    lines.into_iter().rev().enumerate().for_each(|(index, it)| {
-        println!("{index}: {it}");
-        if index == 0 {
-            let num_stacks = it.trim().chars().last().unwrap().to_string().parse::<usize>().unwrap();
-            for _ in 0..num_stacks {
-                stacks.push(LinkedList::new());
-            }
-        } else {
-            it.bytes().collect::<Vec<u8>>().chunks(4)
-                .enumerate().for_each(|(index, it)| {
-                let letter = it[1] as char;
-                if letter != ' ' {
-                    stacks[index].push_back(letter);
-                }
-            });
-        }
-    });
+/// Joins the top crates of all stacks, writing `-` for an empty stack so every crate stays at the
+/// position of its stack.
+fn format_top_crates(warehouse: &Warehouse) -> String {
+    warehouse.top_crates().iter().map(|it| it.unwrap_or("-")).collect()
+}
 
-    stacks
+fn get_stacks_and_instructions(input: String) -> (String, String) {
+    input.split("\n\n").partition(|it| !it.contains("move"))
 }
 
 fn parse_instructions(input: String) -> Vec<Instruction> {
-    input.lines().into_iter().map(Instruction::from).collect()
+    input.lines().map(Instruction::from).collect()
 }
 
-fn execute_instructions(stacks: Vec<LinkedList<char>>, instructions: Vec<Instruction>, runs_on_crate_mover_9001: bool) -> Vec<LinkedList<char>> {
-    let mut stacks = stacks;
-    instructions.into_iter().for_each(|it| {
-        let mut tmp_stack = LinkedList::new();
-        let from = it.from - 1;
-        let to = it.to - 1;
-        for i in 0..it.iterations {
-            let letter = stacks[from].pop_back().unwrap();
-            if runs_on_crate_mover_9001 {
-                tmp_stack.push_front(letter);
-            } else {
-                stacks[to].push_back(letter);
-            }
-        }
-        if runs_on_crate_mover_9001 {
-            stacks[to].append(&mut tmp_stack);
-        }
-    });
+fn execute_instructions(warehouse: Warehouse, instructions: Vec<Instruction>, crane: &mut dyn Crane) -> Result<Warehouse, String> {
+    let mut warehouse = warehouse;
+    for (index, instruction) in instructions.iter().enumerate() {
+        crane.execute(&mut warehouse, instruction).map_err(|error| format!("Instruction {}: {error}", index + 1))?;
+    }
 
-    stacks
+    Ok(warehouse)
 }
 
 struct Instruction {
@@ -78,15 +122,19 @@ struct Instruction {
     pub to: usize,
 }
 
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "move {} from {} to {}", self.iterations, self.from, self.to)
+    }
+}
+
 impl Instruction {
     fn from(input: &str) -> Self {
-        let mut parts = input.split(" ").enumerate();
-        let mut parts = parts.skip(1);
-        let iterations = parts.next().unwrap().1.parse::<usize>().unwrap();
-        let mut parts = parts.skip(1);
-        let from = parts.next().unwrap().1.parse::<usize>().unwrap();
-        let mut parts = parts.skip(1);
-        let to = parts.next().unwrap().1.parse::<usize>().unwrap();
+        let mut parts = input.split(" ").skip(1).step_by(2)
+            .map(|it| it.parse::<usize>().unwrap());
+        let iterations = parts.next().unwrap();
+        let from = parts.next().unwrap();
+        let to = parts.next().unwrap();
         Instruction { iterations, from, to }
     }
 }
@@ -94,10 +142,12 @@ impl Instruction {
 
 #[cfg(test)]
 mod tests {
-    use std::{assert_eq, println, vec};
-    use std::collections::LinkedList;
     use super::*;
 
+    fn warehouse(stacks: &[&str]) -> Warehouse {
+        Warehouse::from_stacks(stacks.iter().map(|it| it.chars().map(|it| it.to_string()).collect()).collect())
+    }
+
     #[test]
     fn prefilters_input() {
         // given: crates and instructions
@@ -129,18 +179,10 @@ move 1 from 1 to 2"#.to_string();
  1   2   3"#.to_string();
 
         // when: the parse function is invoked
-        let mut result = parse_stacks(input);
-        println!("{:?}", result);
+        let result = Warehouse::parse(&input).unwrap();
+
         // then: the stacks are parsed correctly
-        assert_eq!(result[0].len(), 2);
-        assert_eq!(result[0].front().unwrap(), &'Z');
-        assert_eq!(result[0].back().unwrap(), &'N');
-        assert_eq!(result[1].len(), 3);
-        assert_eq!(result[1].pop_front().unwrap(), 'M');
-        assert_eq!(result[1].pop_front().unwrap(), 'C');
-        assert_eq!(result[1].pop_front().unwrap(), 'D');
-        assert_eq!(result[2].len(), 1);
-        assert_eq!(result[2].front().unwrap(), &'P');
+        assert_eq!(result, warehouse(&["ZN", "MCD", "P"]));
     }
 
     #[test]
@@ -172,29 +214,18 @@ move 1 from 1 to 2"#.to_string();
     #[test]
     fn executes_instruction_on_stacks() {
         // given: stacks and instructions
-        let mut stacks = vec![
-            LinkedList::from(['Z', 'N']),
-            LinkedList::from(['M', 'C', 'D']),
-            LinkedList::from(['P']),
-        ];
+        let stacks = warehouse(&["ZN", "MCD", "P"]);
         let instructions = vec![
             Instruction { iterations: 1, from: 2, to: 1 },//znd,mc,p
             Instruction { iterations: 3, from: 1, to: 3 },//,mc,pdnz
         ];
 
         // when: execute_instruction is invoked
-        let mut result = execute_instructions(stacks, instructions, false);
+        let result = execute_instructions(stacks, instructions, &mut CrateMover9000).unwrap();
 
         // then: the crates are stacked correctly
-        assert_eq!(result[0].len(), 0);
-        assert_eq!(result[1].len(), 2);
-        assert_eq!(result[1].pop_front().unwrap(), 'M');
-        assert_eq!(result[1].pop_front().unwrap(), 'C');
-        assert_eq!(result[2].len(), 4);
-        assert_eq!(result[2].pop_front().unwrap(), 'P');
-        assert_eq!(result[2].pop_front().unwrap(), 'D');
-        assert_eq!(result[2].pop_front().unwrap(), 'N');
-        assert_eq!(result[2].pop_front().unwrap(), 'Z');
+        assert_eq!(result, warehouse(&["", "MC", "PDNZ"]));
+        assert_eq!(format_top_crates(&result), "-CZ");
     }
 
     #[test]
@@ -211,39 +242,25 @@ move 2 from 2 to 1
 move 1 from 1 to 2"#.to_string();
 
         // when: restack_crates is invoked
-        let result = restack_crates_and_return_top_crate(input);
+        let result = restack_crates_and_return_top_crate(input, &mut CrateMover9000);
 
         // then: the crates are restacked correctly
-        assert_eq!(result[0], 'C');
-        assert_eq!(result[1], 'M');
-        assert_eq!(result[2], 'Z');
+        assert_eq!(result, Ok("CMZ".to_string()));
     }
 
     #[test]
     fn runs_on_crate_mover_9001() {
         // given: instructions for crate mover 9001
-        let mut stacks = vec![
-            LinkedList::from(['Z', 'N']),
-            LinkedList::from(['M', 'C', 'D']),
-            LinkedList::from(['P']),
-        ];
+        let stacks = warehouse(&["ZN", "MCD", "P"]);
         let instructions = vec![
             Instruction { iterations: 1, from: 2, to: 1 },//znd,mc,p
             Instruction { iterations: 3, from: 1, to: 3 },//,mc,pznd
         ];
 
         // when: execute_instruction is invoked
-        let mut result = execute_instructions(stacks, instructions, true);
+        let result = execute_instructions(stacks, instructions, &mut CrateMover9001).unwrap();
 
         // then: the crates are stacked correctly
-        assert_eq!(result[0].len(), 0);
-        assert_eq!(result[1].len(), 2);
-        assert_eq!(result[1].pop_front().unwrap(), 'M');
-        assert_eq!(result[1].pop_front().unwrap(), 'C');
-        assert_eq!(result[2].len(), 4);
-        assert_eq!(result[2].pop_front().unwrap(), 'P');
-        assert_eq!(result[2].pop_front().unwrap(), 'Z');
-        assert_eq!(result[2].pop_front().unwrap(), 'N');
-        assert_eq!(result[2].pop_front().unwrap(), 'D');
+        assert_eq!(result, warehouse(&["", "MC", "PZND"]));
     }
 }
@@ -0,0 +1,196 @@
+use std::fmt::{Display, Formatter};
+
+/// Stacks of crates, each listed from the bottom to the top, together with the labels written
+/// below them in the drawing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Warehouse {
+    labels: Vec<String>,
+    stacks: Vec<Vec<String>>,
+}
+
+/// A word of a drawing line with the columns it spans, counted in characters.
+struct Token<'a> {
+    start: usize,
+    end: usize,
+    text: &'a str,
+}
+
+impl Token<'_> {
+    /// Twice the center column, which avoids fractions.
+    fn center(&self) -> usize {
+        self.start + self.end
+    }
+}
+
+impl Warehouse {
+    /// Creates a warehouse whose stacks are labeled from 1.
+    #[cfg(test)]
+    pub fn from_stacks(stacks: Vec<Vec<String>>) -> Self {
+        let labels = (1..=stacks.len()).map(|it| it.to_string()).collect();
+        Warehouse { labels, stacks }
+    }
+
+    /// Parses the drawing of the stacks. The last non-empty line holds the labels and every
+    /// crate belongs to the label closest to it, so labels and crate names can be wider than
+    /// a single character and lines may end early or carry trailing whitespace.
+    pub fn parse(input: &str) -> Result<Warehouse, String> {
+        let mut lines = input.lines().filter(|it| !it.trim().is_empty()).collect::<Vec<&str>>();
+        let labels = tokenize(lines.pop().ok_or("The drawing has no stack labels")?);
+        if labels.is_empty() {
+            return Err("The drawing has no stack labels".to_string());
+        }
+        if let Some(label) = labels.iter().find(|it| it.text.starts_with('[') || it.text.ends_with(']')) {
+            return Err(format!("Expected stack labels in the last line but got the crate {}", label.text));
+        }
+        let mut stacks = vec![vec![]; labels.len()];
+        for (row, line) in lines.iter().enumerate().rev() {
+            for token in tokenize(line) {
+                let name = token.text.strip_prefix('[').and_then(|it| it.strip_suffix(']'))
+                    .filter(|it| !it.is_empty())
+                    .ok_or(format!("Expected a crate like [A] in row {} but got {:?}", row + 1, token.text))?;
+                let stack = (0..labels.len()).min_by_key(|it| labels[*it].center().abs_diff(token.center())).unwrap();
+                if stacks[stack].len() != lines.len() - 1 - row {
+                    return Err(format!("Crate [{name}] in row {} is floating above stack {}", row + 1, labels[stack].text));
+                }
+                stacks[stack].push(name.to_string());
+            }
+        }
+        Ok(Warehouse { labels: labels.iter().map(|it| it.text.to_string()).collect(), stacks })
+    }
+
+    /// The crate on top of every stack, or `None` for an empty stack.
+    pub fn top_crates(&self) -> Vec<Option<&str>> {
+        self.stacks.iter().map(|it| it.last().map(|it| it.as_str())).collect()
+    }
+
+    /// Removes the `count` top crates of a stack, keeping their order from bottom to top.
+    pub fn take(&mut self, stack: usize, count: usize) -> Option<Vec<String>> {
+        let stack = self.stacks.get_mut(stack)?;
+        let start = stack.len().checked_sub(count)?;
+        Some(stack.split_off(start))
+    }
+
+    /// Puts crates on top of a stack, the first one at the bottom.
+    pub fn put(&mut self, stack: usize, crates: Vec<String>) -> Option<()> {
+        self.stacks.get_mut(stack)?.extend(crates);
+        Some(())
+    }
+}
+
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    let mut column = 0;
+    for (index, char) in line.char_indices() {
+        match (char.is_whitespace(), start) {
+            (false, None) => start = Some((index, column)),
+            (true, Some((offset, first))) => {
+                tokens.push(Token { start: first, end: column, text: &line[offset..index] });
+                start = None;
+            }
+            _ => {}
+        }
+        column += 1;
+    }
+    if let Some((offset, first)) = start {
+        tokens.push(Token { start: first, end: column, text: &line[offset..] });
+    }
+    tokens
+}
+
+/// Draws the stacks in the format of the puzzle input, widening the columns to fit the longest
+/// crate name or label.
+impl Display for Warehouse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let width = self.stacks.iter().flatten().map(|it| it.chars().count() + 2)
+            .chain(self.labels.iter().map(|it| it.chars().count()))
+            .fold(3, usize::max);
+        let centered = |text: String| {
+            let padding = width - text.chars().count();
+            format!("{}{text}{}", " ".repeat(padding / 2), " ".repeat(padding - padding / 2))
+        };
+        let height = self.stacks.iter().map(|it| it.len()).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            let line = self.stacks.iter()
+                .map(|it| it.get(row).map(|name| centered(format!("[{name}]"))).unwrap_or(" ".repeat(width)))
+                .collect::<Vec<String>>()
+                .join(" ");
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        let labels = self.labels.iter().map(|it| centered(it.clone())).collect::<Vec<String>>().join(" ");
+        write!(f, "{}", labels.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3";
+
+    #[test]
+    fn round_trips_the_drawing() {
+        // given: the example drawing
+        let warehouse = Warehouse::parse(EXAMPLE).unwrap();
+
+        // when: it is drawn again
+        let result = warehouse.to_string();
+
+        // then: we get the same drawing
+        assert_eq!(result, EXAMPLE);
+        assert_eq!(Warehouse::parse(&result), Ok(warehouse));
+    }
+
+    #[test]
+    fn parses_ragged_lines_and_misaligned_labels() {
+        // given: a drawing with trailing whitespace and labels shifted to the left
+        let input = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n1   2   3\n";
+
+        // when: it is parsed
+        let result = Warehouse::parse(input).unwrap();
+
+        // then: every crate is put on the closest stack
+        assert_eq!(result.top_crates(), vec![Some("N"), Some("D"), Some("P")]);
+        assert_eq!(result.stacks[1], vec!["M", "C", "D"]);
+    }
+
+    #[test]
+    fn keeps_empty_stacks_in_the_top_crates() {
+        // given: a drawing whose first stack is empty
+        let warehouse = Warehouse::parse("    [A]\n 1   2").unwrap();
+
+        // when: the top crates are read
+        let result = warehouse.top_crates();
+
+        // then: the crate stays at the position of its stack
+        assert_eq!(result, vec![None, Some("A")]);
+    }
+
+    #[test]
+    fn parses_multi_digit_labels_and_long_crate_names() {
+        // given: eleven stacks and a crate with a long name
+        let mut stacks = (0..11).map(|it| vec![char::from(b'A' + it).to_string()]).collect::<Vec<Vec<String>>>();
+        stacks[10].push("XYZ".to_string());
+        let drawing = Warehouse::from_stacks(stacks.clone()).to_string();
+
+        // when: the drawing is parsed
+        let result = Warehouse::parse(&drawing).unwrap();
+
+        // then: all stacks are found
+        assert_eq!(result.labels.last().unwrap(), "11");
+        assert_eq!(result.stacks, stacks);
+        assert!(drawing.starts_with(&format!("{}[XYZ]\n", " ".repeat(60))));
+    }
+
+    #[test]
+    fn rejects_broken_drawings() {
+        // given: a crate without brackets, a crate floating above an empty stack and crates without labels
+        let inputs = ["[A] B\n 1   2", "    [A]\n[B]\n 1   2", "[A]\n[B]"];
+
+        // when: they are parsed
+        let result = inputs.map(Warehouse::parse);
+
+        // then: both are rejected
+        assert!(result.iter().all(|it| it.is_err()), "{result:?}");
+    }
+}